@@ -1,94 +1,861 @@
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::cpal::FromSample;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sample, Sink, Source};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
-use std::path::PathBuf;
-use std::sync::Mutex;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::oneshot;
 
-pub struct AudioPlayer {
-    _stream: OutputStream,
-    stream_handle: OutputStreamHandle,
-    sink: Mutex<Option<Sink>>,
+/// Uyarı sesi çalarken diğer kanalların düşürüleceği oran
+const DUCK_FACTOR: f32 = 0.3;
+/// Aynı kanalda parça değişirken eski parçanın sönme süresi
+const CROSSFADE_MS: u64 = 800;
+const FADE_STEPS: u32 = 20;
+const DUCK_POLL_MS: u64 = 100;
+/// Varsayılan çıkış cihazının değişip değişmediğini denetleme aralığı
+const DEVICE_POLL_SECS: u64 = 3;
+/// Cihaz değişiminden sonra döngü seslerinin yeniden başlarkenki fade-in süresi
+const RESUME_FADE_MS: u64 = 1500;
+
+/// Üretilen seslerin örnekleme hızı
+const SAMPLE_RATE: u32 = 44_100;
+/// Üretilen gürültünün tepe düzeyi (kanal sesi bunun üzerine uygulanır)
+const NOISE_AMPLITUDE: f32 = 0.25;
+const METRONOME_CLICK_HZ: f32 = 1_000.0;
+const METRONOME_CLICK_MS: u32 = 30;
+const MIN_BPM: u32 = 20;
+const MAX_BPM: u32 = 240;
+
+/// Mikserdeki adlandırılmış kanallar
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    /// Çalışma sırasında tıkırtı döngüsü
+    Ticking,
+    /// Ortam sesleri (mola sesleri vb.)
+    Ambient,
+    /// Oturum sonu ve uyarı sesleri; çalarken diğer kanalları kısar
+    Alerts,
 }
 
-// rodio::OutputStream, cpal::Stream içerir ve PhantomData<*mut ()> nedeniyle !Send/!Sync'tir.
-// Güvenlik gerekçesi:
-// - `_stream`: Sadece lifetime için tutulur, hiçbir yerde erişilmez.
-// - `stream_handle`: OutputStreamHandle dahili olarak Arc tabanlıdır, thread-safe paylaşılır.
-// - `sink`: Mutex<Option<Sink>> ile korunur.
-unsafe impl Send for AudioPlayer {}
-unsafe impl Sync for AudioPlayer {}
+impl Channel {
+    pub const ALL: [Channel; 3] = [Channel::Ticking, Channel::Ambient, Channel::Alerts];
 
-impl AudioPlayer {
-    pub fn new() -> Result<Self, String> {
-        let (stream, handle) =
-            OutputStream::try_default().map_err(|e| format!("Audio init failed: {}", e))?;
-        Ok(Self {
-            _stream: stream,
-            stream_handle: handle,
-            sink: Mutex::new(None),
-        })
+    pub fn name(self) -> &'static str {
+        match self {
+            Channel::Ticking => "ticking",
+            Channel::Ambient => "ambient",
+            Channel::Alerts => "alerts",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Channel::ALL.into_iter().find(|c| c.name() == name)
+    }
+}
+
+/// Üretilen gürültü renkleri
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoiseColor {
+    White,
+    Pink,
+    Brown,
+}
+
+/// Sonsuz, gerçek zamanlı üretilen gürültü kaynağı (dosya gerektirmez)
+struct Noise {
+    color: NoiseColor,
+    /// xorshift32 durumu
+    seed: u32,
+    /// Pembe gürültü filtre durumları (Paul Kellet)
+    pink: [f32; 7],
+    /// Kahverengi gürültü integratörü
+    brown: f32,
+}
+
+impl Noise {
+    fn new(color: NoiseColor) -> Self {
+        Self {
+            color,
+            seed: 0x9E37_79B9,
+            pink: [0.0; 7],
+            brown: 0.0,
+        }
+    }
+
+    /// -1.0 ile 1.0 arası beyaz gürültü örneği
+    fn white(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        (self.seed as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+}
+
+impl Iterator for Noise {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let white = self.white();
+        let sample = match self.color {
+            NoiseColor::White => white,
+            NoiseColor::Pink => {
+                let b = &mut self.pink;
+                b[0] = 0.99886 * b[0] + white * 0.0555179;
+                b[1] = 0.99332 * b[1] + white * 0.0750759;
+                b[2] = 0.96900 * b[2] + white * 0.153852;
+                b[3] = 0.86650 * b[3] + white * 0.3104856;
+                b[4] = 0.55000 * b[4] + white * 0.5329522;
+                b[5] = -0.7616 * b[5] - white * 0.0168980;
+                let pink = b.iter().sum::<f32>() + white * 0.5362;
+                b[6] = white * 0.115926;
+                pink * 0.11
+            }
+            NoiseColor::Brown => {
+                self.brown = (self.brown + 0.02 * white) / 1.02;
+                self.brown * 3.5
+            }
+        };
+        Some(sample * NOISE_AMPLITUDE)
+    }
+}
+
+impl Source for Noise {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
     }
+}
 
-    /// Ses dosyasını oynat (one-shot, fade-in ile)
-    pub fn play(&self, path: &PathBuf, fade_in_ms: u64) -> Result<(), String> {
-        self.stop();
+/// Ayarlanabilir tempolu metronom tıkı; BPM çalarken değiştirilebilir
+struct Metronome {
+    bpm: Arc<AtomicU32>,
+    /// Mevcut vuruş içindeki örnek konumu
+    position: u32,
+}
 
-        let file =
-            File::open(path).map_err(|e| format!("Failed to open sound: {}", e))?;
-        let reader = BufReader::new(file);
-        let source =
-            Decoder::new(reader).map_err(|e| format!("Failed to decode sound: {}", e))?;
+impl Iterator for Metronome {
+    type Item = f32;
 
-        let sink = Sink::try_new(&self.stream_handle)
-            .map_err(|e| format!("Failed to create sink: {}", e))?;
+    fn next(&mut self) -> Option<f32> {
+        let bpm = self.bpm.load(Ordering::Relaxed).clamp(MIN_BPM, MAX_BPM);
+        let beat_len = SAMPLE_RATE * 60 / bpm;
+        if self.position >= beat_len {
+            self.position = 0;
+        }
 
-        if fade_in_ms > 0 {
-            sink.append(source.fade_in(Duration::from_millis(fade_in_ms)));
+        let click_len = SAMPLE_RATE * METRONOME_CLICK_MS / 1000;
+        let sample = if self.position < click_len {
+            // Sönümlenen kısa sinüs
+            let t = self.position as f32 / SAMPLE_RATE as f32;
+            let envelope = 1.0 - self.position as f32 / click_len as f32;
+            (t * METRONOME_CLICK_HZ * std::f32::consts::TAU).sin() * envelope * envelope * 0.5
         } else {
-            sink.append(source);
+            0.0
+        };
+
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl Source for Metronome {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Tek seferlik, sönümlenen sinüs tonu (uyarı çanı, geri sayım tıkı)
+struct Tone {
+    frequency: f32,
+    /// Toplam örnek sayısı
+    length: u32,
+    position: u32,
+}
+
+impl Tone {
+    fn new(frequency: u32, duration_ms: u32) -> Self {
+        Self {
+            frequency: frequency as f32,
+            length: (SAMPLE_RATE * duration_ms / 1000).max(1),
+            position: 0,
+        }
+    }
+}
+
+impl Iterator for Tone {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.position >= self.length {
+            return None;
         }
+        let t = self.position as f32 / SAMPLE_RATE as f32;
+        // Tık sesini önlemek için kısa atak, ardından sönümlenme
+        let attack = (self.position as f32 / (SAMPLE_RATE as f32 * 0.005)).min(1.0);
+        let decay = 1.0 - self.position as f32 / self.length as f32;
+        self.position += 1;
+        Some((t * self.frequency * std::f32::consts::TAU).sin() * attack * decay * decay * 0.5)
+    }
+}
 
-        let mut guard = self.sink.lock().map_err(|_| "Lock failed")?;
-        *guard = Some(sink);
-        Ok(())
+impl Source for Tone {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some((self.length - self.position) as usize)
+    }
+
+    fn channels(&self) -> u16 {
+        1
     }
 
-    /// Ses dosyasını döngüde oynat (ticking sesleri için)
-    pub fn play_loop(&self, path: &PathBuf, fade_in_ms: u64) -> Result<(), String> {
-        self.stop();
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
 
-        let file =
-            File::open(path).map_err(|e| format!("Failed to open sound: {}", e))?;
-        let reader = BufReader::new(file);
-        let source =
-            Decoder::new(reader).map_err(|e| format!("Failed to decode sound: {}", e))?;
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(self.length as f32 / SAMPLE_RATE as f32))
+    }
+}
 
-        let sink = Sink::try_new(&self.stream_handle)
-            .map_err(|e| format!("Failed to create sink: {}", e))?;
+/// Bir kanalda çalınan sesin kaynağı; cihaz değişince döngü sesleri bundan yeniden kurulur
+#[derive(Clone, Debug)]
+enum Playback {
+    File(PathBuf),
+    Noise(NoiseColor),
+    Metronome,
+    Tone { frequency: u32, duration_ms: u32 },
+}
+
+struct ChannelState {
+    volume: f32,
+    sink: Option<Sink>,
+    /// Kanalda istenen döngü sesi (çıkış yokken de hatırlanır)
+    looping: Option<Playback>,
+}
 
-        let looped = source.repeat_infinite();
-        if fade_in_ms > 0 {
-            sink.append(looped.fade_in(Duration::from_millis(fade_in_ms)));
+impl ChannelState {
+    fn new() -> Self {
+        Self {
+            volume: 1.0,
+            sink: None,
+            looping: None,
+        }
+    }
+}
+
+struct Mixer {
+    channels: HashMap<Channel, ChannelState>,
+    /// Uyarı sesi çalıyor, diğer kanallar kısık
+    ducked: bool,
+    /// Metronom temposu (çalan metronom her vuruşta okur)
+    metronome_bpm: Arc<AtomicU32>,
+}
+
+impl Mixer {
+    fn new() -> Self {
+        let channels = Channel::ALL
+            .into_iter()
+            .map(|c| (c, ChannelState::new()))
+            .collect();
+        Self {
+            channels,
+            ducked: false,
+            metronome_bpm: Arc::new(AtomicU32::new(60)),
+        }
+    }
+
+    fn channel(&mut self, channel: Channel) -> &mut ChannelState {
+        self.channels
+            .entry(channel)
+            .or_insert_with(ChannelState::new)
+    }
+
+    /// Kanal sesi ve kısma durumuna göre sink'e uygulanacak ses düzeyi
+    fn effective_volume(&self, channel: Channel) -> f32 {
+        let volume = self.channels.get(&channel).map(|c| c.volume).unwrap_or(1.0);
+        if self.ducked && channel != Channel::Alerts {
+            volume * DUCK_FACTOR
         } else {
-            sink.append(looped);
+            volume
         }
+    }
 
-        let mut guard = self.sink.lock().map_err(|_| "Lock failed")?;
-        *guard = Some(sink);
-        Ok(())
+    fn apply_volumes(&self) {
+        for (channel, state) in &self.channels {
+            if let Some(sink) = &state.sink {
+                sink.set_volume(self.effective_volume(*channel));
+            }
+        }
     }
+}
+
+/// Sink'in sesini kademeli olarak kısıp durdur (arka plan thread'inde)
+fn fade_out_and_stop(sink: Sink, duration_ms: u64) {
+    std::thread::spawn(move || {
+        let start = sink.volume();
+        let step = Duration::from_millis(duration_ms / FADE_STEPS as u64);
+        for i in (0..FADE_STEPS).rev() {
+            sink.set_volume(start * i as f32 / FADE_STEPS as f32);
+            std::thread::sleep(step);
+        }
+        sink.stop();
+    });
+}
+
+/// Ses çıkışının durumu; değiştikçe frontend'e "audio-status" olayıyla bildirilir
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioStatus {
+    /// Bir çıkış cihazı açık
+    pub available: bool,
+    pub device: Option<String>,
+    /// Son açma denemesinin hatası (cihaz yoksa veya açılamadıysa)
+    pub error: Option<String>,
+}
+
+/// Açık ses çıkışı
+struct Output {
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+    device: String,
+}
 
-    /// Sesi durdur
-    pub fn stop(&self) {
-        match self.sink.lock() {
-            Ok(mut guard) => {
-                if let Some(sink) = guard.take() {
-                    sink.stop();
+fn default_device_name() -> Option<String> {
+    rodio::cpal::default_host()
+        .default_output_device()
+        .and_then(|d| d.name().ok())
+}
+
+/// Varsayılan çıkış cihazını aç
+fn open_output() -> Result<Output, String> {
+    let device = rodio::cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| "No audio output device".to_string())?;
+    let name = device.name().unwrap_or_else(|_| "unknown".to_string());
+    let (stream, handle) = OutputStream::try_from_device(&device)
+        .map_err(|e| format!("Audio init failed: {}", e))?;
+    Ok(Output {
+        _stream: stream,
+        handle,
+        device: name,
+    })
+}
+
+/// Kaynağı (isteğe bağlı fade-in ile) sink'e ekle
+fn append_source<S>(sink: &Sink, source: S, fade_in_ms: u64)
+where
+    S: Source + Send + 'static,
+    S::Item: Sample + Send,
+    f32: FromSample<S::Item>,
+{
+    if fade_in_ms > 0 {
+        sink.append(source.fade_in(Duration::from_millis(fade_in_ms)));
+    } else {
+        sink.append(source);
+    }
+}
+
+/// Ses thread'ine gönderilen komutlar
+enum AudioCommand {
+    Play {
+        channel: Channel,
+        playback: Playback,
+        looped: bool,
+        fade_in_ms: u64,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+    Stop(Channel),
+    SetVolume(Channel, f32),
+    SetMetronomeBpm(u32),
+    Volumes(oneshot::Sender<HashMap<Channel, f32>>),
+    Status(oneshot::Sender<AudioStatus>),
+}
+
+/// Ses thread'inin durumu. OutputStream (!Send) ve sink'ler yalnızca bu thread'de yaşar.
+/// Çıkış cihazı ilk ses çalınırken açılır; cihaz yoksa null backend gibi davranır
+/// (sesler sessizce atlanır, döngü sesleri hatırlanır) ve cihaz değişince/geri gelince
+/// çıkış yeniden açılıp döngü sesleri sürdürülür.
+struct AudioThread {
+    app_handle: AppHandle,
+    /// None: henüz açılmadı veya cihaz yok
+    output: Option<Output>,
+    status: AudioStatus,
+    mixer: Mixer,
+    last_device_check: Instant,
+}
+
+impl AudioThread {
+    fn new(app_handle: AppHandle) -> Self {
+        Self {
+            app_handle,
+            output: None,
+            status: AudioStatus::default(),
+            mixer: Mixer::new(),
+            last_device_check: Instant::now(),
+        }
+    }
+
+    /// Komut kuyruğu kapanana kadar çalış
+    fn run(mut self, commands: Receiver<AudioCommand>) {
+        loop {
+            // Uyarı sesi çalarken kısmayı kaldırmak için daha sık uyan
+            let timeout = if self.mixer.ducked {
+                Duration::from_millis(DUCK_POLL_MS)
+            } else {
+                Duration::from_secs(DEVICE_POLL_SECS)
+            };
+
+            match commands.recv_timeout(timeout) {
+                Ok(command) => self.handle(command),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            self.check_alert();
+            if self.last_device_check.elapsed() >= Duration::from_secs(DEVICE_POLL_SECS) {
+                self.last_device_check = Instant::now();
+                self.check_device();
+            }
+        }
+    }
+
+    fn handle(&mut self, command: AudioCommand) {
+        match command {
+            AudioCommand::Play {
+                channel,
+                playback,
+                looped,
+                fade_in_ms,
+                reply,
+            } => {
+                let result = self.play(channel, playback, looped, fade_in_ms);
+                let _ = reply.send(result);
+            }
+            AudioCommand::Stop(channel) => self.stop(channel),
+            AudioCommand::SetVolume(channel, volume) => {
+                self.mixer.channel(channel).volume = volume.clamp(0.0, 1.0);
+                self.mixer.apply_volumes();
+            }
+            AudioCommand::SetMetronomeBpm(bpm) => self
+                .mixer
+                .metronome_bpm
+                .store(bpm.clamp(MIN_BPM, MAX_BPM), Ordering::Relaxed),
+            AudioCommand::Volumes(reply) => {
+                let volumes = self
+                    .mixer
+                    .channels
+                    .iter()
+                    .map(|(channel, state)| (*channel, state.volume))
+                    .collect();
+                let _ = reply.send(volumes);
+            }
+            AudioCommand::Status(reply) => {
+                let _ = reply.send(self.status.clone());
+            }
+        }
+    }
+
+    fn set_status(&mut self, status: AudioStatus) {
+        if self.status == status {
+            return;
+        }
+        if let Some(error) = &status.error {
+            eprintln!("Ses çıkışı kullanılamıyor: {}", error);
+        }
+        self.status = status.clone();
+        if let Err(e) = self.app_handle.emit("audio-status", status) {
+            eprintln!("Ses durumu iletilemedi: {}", e);
+        }
+    }
+
+    /// Açık çıkışın handle'ı; yoksa varsayılan cihazı açmayı dener
+    fn ensure_output(&mut self) -> Option<OutputStreamHandle> {
+        if let Some(o) = &self.output {
+            return Some(o.handle.clone());
+        }
+
+        match open_output() {
+            Ok(o) => {
+                let handle = o.handle.clone();
+                self.set_status(AudioStatus {
+                    available: true,
+                    device: Some(o.device.clone()),
+                    error: None,
+                });
+                self.output = Some(o);
+                Some(handle)
+            }
+            Err(e) => {
+                self.set_status(AudioStatus {
+                    available: false,
+                    device: None,
+                    error: Some(e),
+                });
+                None
+            }
+        }
+    }
+
+    /// Varsayılan cihaz değiştiyse veya kaybolduysa çıkışı yeniden aç
+    fn check_device(&mut self) {
+        // Açık çıkış ve bekleyen döngü sesi yoksa cihazları boşuna tarama
+        let looping = self.mixer.channels.values().any(|c| c.looping.is_some());
+        if self.output.is_none() && !looping {
+            return;
+        }
+        let current = default_device_name();
+        match self.output.as_ref().map(|o| o.device.clone()) {
+            Some(device) if current.as_ref() != Some(&device) => {
+                eprintln!(
+                    "Varsayılan ses cihazı değişti ({} → {}), çıkış yeniden açılıyor",
+                    device,
+                    current.as_deref().unwrap_or("yok")
+                );
+                self.reinit();
+            }
+            // Cihaz yokken bekleyen döngü sesleri varsa cihaz gelince başlat
+            None if current.is_some() && looping => self.reinit(),
+            _ => {}
+        }
+    }
+
+    /// Çıkışı kapat, yeniden aç ve döngü seslerini sürdür
+    fn reinit(&mut self) {
+        self.mixer.ducked = false;
+        let loops: Vec<(Channel, Playback)> = self
+            .mixer
+            .channels
+            .iter_mut()
+            .filter_map(|(channel, state)| {
+                state.sink = None;
+                state.looping.clone().map(|p| (*channel, p))
+            })
+            .collect();
+        self.output = None;
+
+        if self.ensure_output().is_none() {
+            return;
+        }
+        for (channel, playback) in loops {
+            if let Err(e) = self.play(channel, playback, true, RESUME_FADE_MS) {
+                eprintln!("{} kanalı yeniden başlatılamadı: {}", channel.name(), e);
+            }
+        }
+    }
+
+    /// Kanalda çalan bir ses varsa eskisi sönerken yenisi yükselir (crossfade).
+    /// Alerts kanalında çalan ses bitene kadar diğer kanallar kısılır.
+    fn play(
+        &mut self,
+        channel: Channel,
+        playback: Playback,
+        looped: bool,
+        fade_in_ms: u64,
+    ) -> Result<(), String> {
+        let Some(handle) = self.ensure_output() else {
+            // Çıkış yok: sesi atla, döngü sesini cihaz gelince başlatmak üzere hatırla
+            self.mixer.channel(channel).looping = looped.then_some(playback);
+            return Ok(());
+        };
+
+        let sink =
+            Sink::try_new(&handle).map_err(|e| format!("Failed to create sink: {}", e))?;
+
+        // Yalnızca hâlâ çalan bir ses varsa crossfade yap
+        let crossfade = self
+            .mixer
+            .channel(channel)
+            .sink
+            .as_ref()
+            .is_some_and(|s| !s.empty());
+        let fade_in_ms = if crossfade && fade_in_ms == 0 {
+            CROSSFADE_MS
+        } else {
+            fade_in_ms
+        };
+
+        match &playback {
+            Playback::File(path) => {
+                let file =
+                    File::open(path).map_err(|e| format!("Failed to open sound: {}", e))?;
+                let source = Decoder::new(BufReader::new(file))
+                    .map_err(|e| format!("Failed to decode sound: {}", e))?;
+                if looped {
+                    append_source(&sink, source.repeat_infinite(), fade_in_ms);
+                } else {
+                    append_source(&sink, source, fade_in_ms);
                 }
             }
-            Err(e) => eprintln!("AudioPlayer::stop mutex poisoned: {}", e),
+            Playback::Noise(color) => append_source(&sink, Noise::new(*color), fade_in_ms),
+            Playback::Metronome => {
+                let bpm = self.mixer.metronome_bpm.clone();
+                append_source(&sink, Metronome { bpm, position: 0 }, fade_in_ms);
+            }
+            Playback::Tone {
+                frequency,
+                duration_ms,
+            } => append_source(&sink, Tone::new(*frequency, *duration_ms), fade_in_ms),
         }
+
+        // Yalnızca tek seferlik uyarılar kısar; döngüdeki uyarı hiç bitmeyeceğinden
+        // kısmayı kalıcı yapardı
+        if channel == Channel::Alerts && self.mixer.ducked == looped {
+            self.mixer.ducked = !looped;
+            self.mixer.apply_volumes();
+        }
+        sink.set_volume(self.mixer.effective_volume(channel));
+
+        let state = self.mixer.channel(channel);
+        state.looping = looped.then_some(playback);
+        if let Some(old) = state.sink.replace(sink) {
+            fade_out_and_stop(old, CROSSFADE_MS);
+        }
+        Ok(())
     }
 
+    /// Uyarı sesi bitince kısılan kanalları eski düzeyine getir
+    fn check_alert(&mut self) {
+        if !self.mixer.ducked {
+            return;
+        }
+        let playing = self
+            .mixer
+            .channels
+            .get(&Channel::Alerts)
+            .and_then(|c| c.sink.as_ref())
+            .is_some_and(|s| !s.empty());
+        if !playing {
+            self.mixer.ducked = false;
+            self.mixer.apply_volumes();
+        }
+    }
+
+    fn stop(&mut self, channel: Channel) {
+        let state = self.mixer.channel(channel);
+        state.looping = None;
+        if let Some(sink) = state.sink.take() {
+            sink.stop();
+        }
+        if channel == Channel::Alerts && self.mixer.ducked {
+            self.mixer.ducked = false;
+            self.mixer.apply_volumes();
+        }
+    }
+}
+
+/// Ses sistemine erişim. Çıkış ve sink'ler ayrı bir thread'de tutulur; bu tür
+/// yalnızca o thread'e komut gönderir, bu yüzden klonlanıp her yerden kullanılabilir.
+#[derive(Clone)]
+pub struct AudioPlayer {
+    commands: Sender<AudioCommand>,
+}
+
+impl AudioPlayer {
+    pub fn new(app_handle: AppHandle) -> Self {
+        let (commands, receiver) = mpsc::channel();
+        let spawned = std::thread::Builder::new()
+            .name("audio".to_string())
+            .spawn(move || AudioThread::new(app_handle).run(receiver));
+        if let Err(e) = spawned {
+            eprintln!("Ses thread'i başlatılamadı: {}", e);
+        }
+        Self { commands }
+    }
+
+    fn send(&self, command: AudioCommand) {
+        if self.commands.send(command).is_err() {
+            eprintln!("Ses thread'i çalışmıyor, komut atlandı");
+        }
+    }
+
+    /// Komutu gönder ve yanıtı bekle. Yanıt async beklenir; ses thread'i meşgulken
+    /// (ör. cihaz açılırken) çağıran tokio thread'i bloklanmaz.
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> AudioCommand) -> Option<T> {
+        let (reply, response) = oneshot::channel();
+        self.send(command(reply));
+        response.await.ok()
+    }
+
+    async fn play_request(
+        &self,
+        channel: Channel,
+        playback: Playback,
+        looped: bool,
+        fade_in_ms: u64,
+    ) -> Result<(), String> {
+        self.request(|reply| AudioCommand::Play {
+            channel,
+            playback,
+            looped,
+            fade_in_ms,
+            reply,
+        })
+        .await
+        .unwrap_or_else(|| Err("Audio thread is not running".to_string()))
+    }
+
+    /// Ses dosyasını kanalda oynat
+    pub async fn play(
+        &self,
+        channel: Channel,
+        path: &Path,
+        looped: bool,
+        fade_in_ms: u64,
+    ) -> Result<(), String> {
+        self.play_request(channel, Playback::File(path.to_path_buf()), looped, fade_in_ms)
+            .await
+    }
+
+    /// Üretilen gürültüyü ortam kanalında çal
+    pub async fn play_noise(&self, color: NoiseColor) -> Result<(), String> {
+        self.play_request(Channel::Ambient, Playback::Noise(color), true, 1500)
+            .await
+    }
+
+    /// Metronomu tıkırtı kanalında çal (tempo: set_metronome_bpm)
+    pub async fn play_metronome(&self) -> Result<(), String> {
+        self.play_request(Channel::Ticking, Playback::Metronome, true, 0)
+            .await
+    }
+
+    /// Üretilen kısa bir tonu uyarı kanalında çal
+    pub async fn play_tone(&self, frequency: u32, duration_ms: u32) -> Result<(), String> {
+        self.play_request(
+            Channel::Alerts,
+            Playback::Tone {
+                frequency,
+                duration_ms,
+            },
+            false,
+            0,
+        )
+        .await
+    }
+
+    /// Metronom temposunu ayarla; çalan metronom bir sonraki vuruştan itibaren uyar
+    pub fn set_metronome_bpm(&self, bpm: u32) {
+        self.send(AudioCommand::SetMetronomeBpm(bpm));
+    }
+
+    /// Kanaldaki sesi durdur
+    pub fn stop(&self, channel: Channel) {
+        self.send(AudioCommand::Stop(channel));
+    }
+
+    /// Tüm kanalları durdur
+    pub fn stop_all(&self) {
+        for channel in Channel::ALL {
+            self.stop(channel);
+        }
+    }
+
+    /// Kanal ses düzeyini ayarla (0.0 - 1.0)
+    pub fn set_volume(&self, channel: Channel, volume: f32) {
+        self.send(AudioCommand::SetVolume(channel, volume));
+    }
+
+    /// Tüm kanalların ses düzeyleri
+    pub async fn volumes(&self) -> HashMap<Channel, f32> {
+        self.request(AudioCommand::Volumes).await.unwrap_or_default()
+    }
+
+    pub async fn status(&self) -> AudioStatus {
+        self.request(AudioCommand::Status).await.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_range(samples: impl Iterator<Item = f32>) -> bool {
+        samples.into_iter().all(|s| (-1.0..=1.0).contains(&s))
+    }
+
+    /// Ardışık tıkların başlangıçları arasındaki örnek sayıları
+    fn click_periods(metronome: &mut Metronome, samples: usize) -> Vec<usize> {
+        let mut starts = Vec::new();
+        let mut silent = true;
+        for (i, sample) in metronome.take(samples).enumerate() {
+            if sample != 0.0 && silent {
+                starts.push(i);
+            }
+            silent = sample == 0.0;
+        }
+        starts.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    #[test]
+    fn noise_stays_in_range() {
+        for color in [NoiseColor::White, NoiseColor::Pink, NoiseColor::Brown] {
+            assert!(in_range(Noise::new(color).take(SAMPLE_RATE as usize * 10)), "{:?}", color);
+        }
+    }
+
+    #[test]
+    fn tone_ends_after_its_duration() {
+        let tone = Tone::new(880, 250);
+        assert_eq!(tone.total_duration(), Some(Duration::from_millis(250)));
+        assert_eq!(tone.count(), SAMPLE_RATE as usize / 4);
+        assert!(in_range(Tone::new(880, 250)));
+        // Sıfır süre yine de tek örnek üretir
+        assert_eq!(Tone::new(880, 0).count(), 1);
+    }
+
+    #[test]
+    fn metronome_period_follows_bpm() {
+        let bpm = Arc::new(AtomicU32::new(120));
+        let mut metronome = Metronome {
+            bpm: bpm.clone(),
+            position: 0,
+        };
+
+        let periods = click_periods(&mut metronome, SAMPLE_RATE as usize * 3);
+        assert!(!periods.is_empty());
+        assert!(periods.iter().all(|p| *p == SAMPLE_RATE as usize / 2));
+
+        // Çalarken değişen tempo sonraki vuruşlardan itibaren uygulanır
+        bpm.store(60, Ordering::Relaxed);
+        let periods = click_periods(&mut metronome, SAMPLE_RATE as usize * 4);
+        assert!(periods.len() >= 3);
+        assert!(periods.iter().skip(1).all(|p| *p == SAMPLE_RATE as usize));
+
+        // Aralık dışı tempolar sınırlanır
+        bpm.store(1000, Ordering::Relaxed);
+        let periods = click_periods(&mut metronome, SAMPLE_RATE as usize * 2);
+        assert!(periods.len() >= 3);
+        assert!(periods.iter().skip(1).all(|p| *p == (SAMPLE_RATE * 60 / MAX_BPM) as usize));
+        assert!(in_range(metronome.take(SAMPLE_RATE as usize)));
+    }
 }
@@ -1,11 +1,36 @@
-use clap::{Parser, Subcommand};
+use crate::audio::Channel;
+use crate::display::Backend;
+use crate::socket::{self, SocketRequest};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::process::{Command, Stdio};
+use std::time::Duration;
 use zbus::Connection;
 
+const BUS_NAME: &str = "com.osmandulundu.pomodoro";
+
+/// --spawn ile başlatılan uygulamanın hazır olması için beklenecek en uzun süre
+const SPAWN_TIMEOUT: Duration = Duration::from_secs(15);
+
 #[derive(Parser)]
 #[command(name = "pomodoro", about = "pomodoro - CachyOS")]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+    /// GDK arka ucu (varsayılan: yapılandırma dosyası, yoksa oturum türüne göre)
+    #[arg(long, value_enum)]
+    pub backend: Option<Backend>,
+    /// Uygulama çalışmıyorsa arka planda başlat ve komutu ona ilet
+    #[arg(long, global = true)]
+    pub spawn: bool,
+    /// Pencereyi açmadan tray'de başla
+    #[arg(long)]
+    pub minimized: bool,
+    /// Son çıkıştaki gibi başla: pencere gizliyse tray'de kal (otomatik başlatma, oturum geri yükleme)
+    #[arg(long)]
+    pub restore: bool,
+    /// D-Bus/systemd tarafından başlatıldı: pencereyi açmadan tray'de başla
+    #[arg(long, hide = true)]
+    pub activated: bool,
 }
 
 #[derive(Subcommand)]
@@ -27,67 +52,273 @@ pub enum Commands {
     },
     /// Timer durumunu göster
     Status,
+    /// Ses düzeylerini göster veya bir kanalın ses düzeyini ayarla (0-100)
+    Volume {
+        #[arg(value_parser = ["ticking", "ambient", "alerts"])]
+        channel: Option<String>,
+        #[arg(value_parser = clap::value_parser!(u8).range(0..=100))]
+        level: Option<u8>,
+    },
 }
 
-/// D-Bus üzerinden çalışan uygulamaya komut gönder.
-/// Başarılı olursa true döner (uygulama çalışıyordu), false döner (bağlantı yok).
-pub async fn handle_cli(command: &Commands) -> Result<bool, Box<dyn std::error::Error>> {
-    let conn = match Connection::session().await {
-        Ok(c) => c,
-        Err(_) => return Ok(false),
+/// Timer durumunu okunabilir biçimde yazdır
+fn print_status(state: &str, time_left: u32, is_active: bool, sessions: u32) {
+    let mins = time_left / 60;
+    let secs = time_left % 60;
+    let status = if is_active { "Çalışıyor" } else { "Duraklatıldı" };
+    let mode_tr = match state {
+        "work" => "Odaklan",
+        "shortBreak" => "Kısa Mola",
+        "longBreak" => "Uzun Mola",
+        _ => state,
     };
 
-    let proxy: zbus::Proxy<'_> = zbus::proxy::Builder::new(&conn)
-        .destination("com.osmandulundu.pomodoro")?
-        .path("/com/osmandulundu/pomodoro")?
-        .interface("com.osmandulundu.pomodoro")?
-        .build()
-        .await?;
+    println!("Mod: {} | {} | {:02}:{:02} | Oturum: {}", mode_tr, status, mins, secs, sessions);
+}
 
+/// Kanal ses düzeylerini yüzde olarak yazdır
+fn print_volumes(volumes: &[(Channel, f64)]) {
+    for (channel, volume) in volumes {
+        println!("{:<8} {:>3}%", channel.name(), (volume * 100.0).round() as u32);
+    }
+}
+
+/// Komutun başarı mesajı
+fn success_message(command: &Commands) -> String {
     match command {
-        Commands::Toggle => {
-            proxy.call_noreply("Toggle", &()).await?;
-            println!("Timer toggled.");
-        }
-        Commands::Start => {
-            proxy.call_noreply("Start", &()).await?;
-            println!("Timer started.");
-        }
-        Commands::Stop => {
-            proxy.call_noreply("Stop", &()).await?;
-            println!("Timer stopped.");
+        Commands::Toggle => "Timer toggled.".to_string(),
+        Commands::Start => "Timer started.".to_string(),
+        Commands::Stop => "Timer stopped.".to_string(),
+        Commands::Skip => "Session skipped.".to_string(),
+        Commands::Reset => "Timer reset.".to_string(),
+        Commands::Extend { seconds } => format!("Timer extended by {} seconds.", seconds),
+        Commands::Volume {
+            channel: Some(channel),
+            level: Some(level),
+        } => format!("{} volume set to {}%.", channel, level),
+        Commands::Status | Commands::Volume { .. } => String::new(),
+    }
+}
+
+/// Çalışan uygulamaya komut gönder.
+/// Önce oturum D-Bus'ı denenir; bus yoksa veya uygulama bu bus'ta değilse (ör. farklı
+/// DBUS_SESSION_BUS_ADDRESS ile başlatıldı) Unix soketine ($XDG_RUNTIME_DIR/pomodoro.sock)
+/// düşülür. Başarılı olursa true döner (uygulama çalışıyordu), false döner (bağlantı yok).
+pub async fn handle_cli(command: &Commands) -> Result<bool, Box<dyn std::error::Error>> {
+    if let Ok(conn) = Connection::session().await {
+        if handle_cli_dbus(&conn, command).await? {
+            return Ok(true);
         }
-        Commands::Skip => {
-            proxy.call_noreply("Skip", &()).await?;
-            println!("Session skipped.");
+    }
+    handle_cli_socket(command).await
+}
+
+/// D-Bus adının şu an bir sahibi var mı (uygulama çalışıyor mu)
+async fn is_running(conn: &Connection) -> bool {
+    match zbus::fdo::DBusProxy::new(conn).await {
+        Ok(proxy) => proxy
+            .name_has_owner(BUS_NAME.try_into().expect("valid name"))
+            .await
+            .unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+/// Uygulama çalışıyor ya da D-Bus tarafından başlatılabilir mi
+/// (com.osmandulundu.pomodoro.service kuruluysa bus ilk çağrıda başlatır)
+async fn is_available(conn: &Connection) -> bool {
+    if is_running(conn).await {
+        return true;
+    }
+    match zbus::fdo::DBusProxy::new(conn).await {
+        Ok(proxy) => proxy
+            .list_activatable_names()
+            .await
+            .is_ok_and(|names| names.iter().any(|name| name.as_str() == BUS_NAME)),
+        Err(_) => false,
+    }
+}
+
+/// Çalışan bir örnek varsa penceresini göster (tek örnek kontrolü).
+/// D-Bus adının sahibi varsa ShowWindow çağrılır, yoksa (veya oturum bus'ı yoksa)
+/// sokete "show" isteği gönderilir. Örnek bulunduysa true döner. `show` false ise
+/// (--minimized, --restore) yalnızca örneğin varlığı denetlenir, pencereye dokunulmaz.
+pub async fn activate_running(show: bool) -> bool {
+    if let Ok(conn) = Connection::session().await {
+        if is_running(&conn).await {
+            if !show {
+                return true;
+            }
+            let result = async {
+                zbus::Proxy::new(&conn, BUS_NAME, "/com/osmandulundu/pomodoro", BUS_NAME)
+                    .await?
+                    .call::<_, _, ()>("ShowWindow", &())
+                    .await
+            }
+            .await;
+            if let Err(e) = result {
+                eprintln!("Çalışan uygulamanın penceresi gösterilemedi: {}", e);
+            }
+            // Ad sahipli: pencere gösterilemese de ikinci bir örnek başlatılmaz
+            return true;
         }
-        Commands::Reset => {
-            proxy.call_noreply("Reset", &()).await?;
-            println!("Timer reset.");
+    }
+
+    let request = if show {
+        SocketRequest::Show
+    } else {
+        SocketRequest::Status
+    };
+    socket::request(&request)
+        .await
+        .is_ok_and(|response| response.ok)
+}
+
+/// GUI'yi ayrı bir süreç olarak arka planda başlat
+fn spawn_gui(backend: Option<Backend>) -> std::io::Result<()> {
+    let mut command = Command::new(std::env::current_exe()?);
+    // Komut için başlatılan örnek pencere açmadan tray'de başlar
+    command.arg("--minimized");
+    if let Some(backend) = backend {
+        if let Some(value) = backend.to_possible_value() {
+            command.arg("--backend").arg(value.get_name());
         }
-        Commands::Extend { seconds } => {
-            proxy.call_noreply("Extend", &(*seconds,)).await?;
-            println!("Timer extended by {} seconds.", seconds);
+    }
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Terminal kapandığında uygulama da kapanmasın
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    command.spawn().map(|_| ())
+}
+
+/// Uygulama çalışmıyorsa başlat, hazır olana kadar bekleyip komutu gönder.
+/// Süre içinde uygulamaya ulaşılamazsa false döner.
+pub async fn handle_cli_spawn(
+    command: &Commands,
+    backend: Option<Backend>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    if handle_cli(command).await? {
+        return Ok(true);
+    }
+
+    spawn_gui(backend)?;
+    let deadline = tokio::time::Instant::now() + SPAWN_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        // Komutlar arayüz hazır olana kadar uygulamada kuyrukta bekler
+        if handle_cli(command).await? {
+            return Ok(true);
         }
+    }
+    Ok(false)
+}
+
+async fn handle_cli_dbus(
+    conn: &Connection,
+    command: &Commands,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    // call_noreply sahipsiz ada giden çağrılarda hata vermez
+    if !is_available(conn).await {
+        return Ok(false);
+    }
+
+    let proxy: zbus::Proxy<'_> = zbus::proxy::Builder::new(conn)
+        .destination(BUS_NAME)?
+        .path("/com/osmandulundu/pomodoro")?
+        .interface(BUS_NAME)?
+        .build()
+        .await?;
+
+    match command {
+        Commands::Toggle => proxy.call_noreply("Toggle", &()).await?,
+        Commands::Start => proxy.call_noreply("Start", &()).await?,
+        Commands::Stop => proxy.call_noreply("Stop", &()).await?,
+        Commands::Skip => proxy.call_noreply("Skip", &()).await?,
+        Commands::Reset => proxy.call_noreply("Reset", &()).await?,
+        Commands::Extend { seconds } => proxy.call_noreply("Extend", &(*seconds,)).await?,
         Commands::Status => {
             let state: String = proxy.get_property("State").await?;
             let time_left: u32 = proxy.get_property("TimeLeft").await?;
             let is_active: bool = proxy.get_property("IsActive").await?;
             let sessions: u32 = proxy.get_property("SessionsCompleted").await?;
 
-            let mins = time_left / 60;
-            let secs = time_left % 60;
-            let status = if is_active { "Çalışıyor" } else { "Duraklatıldı" };
-            let mode_tr = match state.as_str() {
-                "work" => "Odaklan",
-                "shortBreak" => "Kısa Mola",
-                "longBreak" => "Uzun Mola",
-                _ => &state,
+            print_status(&state, time_left, is_active, sessions);
+            return Ok(true);
+        }
+        Commands::Volume {
+            channel: Some(channel),
+            level: Some(level),
+        } => {
+            proxy
+                .call::<_, _, ()>("SetVolume", &(channel.as_str(), *level as f64 / 100.0))
+                .await?
+        }
+        Commands::Volume { channel, .. } => {
+            // Kanal verilmişse yalnızca onu, verilmemişse tüm kanalları göster
+            let channels: Vec<Channel> = match channel.as_deref().and_then(Channel::from_name) {
+                Some(c) => vec![c],
+                None => Channel::ALL.to_vec(),
             };
-
-            println!("Mod: {} | {} | {:02}:{:02} | Oturum: {}", mode_tr, status, mins, secs, sessions);
+            let mut volumes = Vec::new();
+            for c in channels {
+                let volume: f64 = proxy.call("GetVolume", &(c.name(),)).await?;
+                volumes.push((c, volume));
+            }
+            print_volumes(&volumes);
+            return Ok(true);
         }
     }
 
+    println!("{}", success_message(command));
+    Ok(true)
+}
+
+async fn handle_cli_socket(command: &Commands) -> Result<bool, Box<dyn std::error::Error>> {
+    let request = match command {
+        Commands::Toggle => SocketRequest::Toggle,
+        Commands::Start => SocketRequest::Start,
+        Commands::Stop => SocketRequest::Stop,
+        Commands::Skip => SocketRequest::Skip,
+        Commands::Reset => SocketRequest::Reset,
+        Commands::Extend { seconds } => SocketRequest::Extend { seconds: *seconds },
+        Commands::Status => SocketRequest::Status,
+        Commands::Volume { channel, level } => SocketRequest::Volume {
+            channel: channel.as_deref().and_then(Channel::from_name),
+            volume: level.map(|l| l as f32 / 100.0),
+        },
+    };
+
+    let response = match socket::request(&request).await {
+        Ok(r) => r,
+        // Soket yoksa veya dinleyen yoksa uygulama çalışmıyordur
+        Err(_) => return Ok(false),
+    };
+
+    if !response.ok {
+        return Err(response.error.unwrap_or_else(|| "Unknown error".to_string()).into());
+    }
+
+    if let Some(s) = response.status {
+        print_status(&s.state, s.time_left, s.is_active, s.sessions_completed);
+        return Ok(true);
+    }
+
+    match (command, response.volumes) {
+        // Ses düzeyi okuma: istenen kanal veya tüm kanallar
+        (Commands::Volume { channel, level: None }, Some(volumes)) => {
+            let wanted = channel.as_deref().and_then(Channel::from_name);
+            let volumes: Vec<(Channel, f64)> = Channel::ALL
+                .into_iter()
+                .filter(|c| wanted.is_none_or(|w| w == *c))
+                .map(|c| (c, volumes.get(&c).copied().unwrap_or(1.0) as f64))
+                .collect();
+            print_volumes(&volumes);
+        }
+        _ => println!("{}", success_message(command)),
+    }
     Ok(true)
 }
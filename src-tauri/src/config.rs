@@ -0,0 +1,114 @@
+use crate::display::Backend;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Kullanıcı yapılandırma dosyası: $XDG_CONFIG_HOME/pomodoro/config.json
+/// Arayüzde bulunmayan, betik ve entegrasyonlara yönelik ayarları içerir.
+/// Dosya yoksa veya okunamazsa varsayılanlar kullanılır.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub hooks: HookConfig,
+    pub http: HttpConfig,
+    pub display: DisplayConfig,
+    pub media: MediaConfig,
+}
+
+/// Timer olaylarında çalıştırılacak kullanıcı komutları
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct HookConfig {
+    /// Tek bir komutun en fazla çalışma süresi (saniye)
+    pub timeout_secs: u64,
+    /// Aynı anda çalışabilecek en fazla komut sayısı
+    pub max_concurrent: usize,
+    /// Hooks dizinindeki betiklere ek olarak çalıştırılacak komutlar
+    pub commands: Vec<HookCommand>,
+}
+
+impl Default for HookConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 10,
+            max_concurrent: 4,
+            commands: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HookCommand {
+    pub command: PathBuf,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Boşsa tüm olaylarda çalışır
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// Yerel HTTP/JSON API (yalnızca 127.0.0.1 üzerinde dinler)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// İsteklerde `Authorization: Bearer <token>` veya `?token=` ile gönderilmeli
+    pub token: String,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 7425,
+            token: String::new(),
+        }
+    }
+}
+
+/// Pencere sistemi ayarları (bkz. display.rs); `--backend` bayrağı bunu geçersiz kılar
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    pub backend: Backend,
+}
+
+/// Medya oynatıcı kontrolü (bkz. media.rs)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MediaConfig {
+    /// "Çalışmada Çal" ile başlatılacak oynatıcı, ör. "spotify"
+    /// (org.mpris.MediaPlayer2. öneki olmadan)
+    pub player: Option<String>,
+}
+
+/// $XDG_CONFIG_HOME/pomodoro (varsayılan ~/.config/pomodoro)
+pub fn config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("pomodoro"))
+}
+
+impl AppConfig {
+    pub fn load() -> Self {
+        let Some(path) = config_dir().map(|dir| dir.join("config.json")) else {
+            return Self::default();
+        };
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                eprintln!("Yapılandırma okunamadı ({}): {}", path.display(), e);
+                return Self::default();
+            }
+        };
+
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            eprintln!("Yapılandırma ayrıştırılamadı ({}): {}", path.display(), e);
+            Self::default()
+        })
+    }
+}
@@ -0,0 +1,292 @@
+use crate::audio::{AudioPlayer, Channel};
+use crate::dbus::{SharedTimerState, TimerStatus};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Frontend hazır değilken sırada tutulabilecek en fazla komut
+const MAX_PENDING: usize = 16;
+
+/// Harici kontrol yüzeylerinden (tray, global kısayol, D-Bus, MPRIS, soket, HTTP,
+/// bildirim eylemleri, boşta algılama) gelen timer komutları. Timer motoru
+/// frontend'de çalıştığı için komutlar tek bir "timer-command" event'i olarak iletilir.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "command", content = "seconds", rename_all = "camelCase")]
+pub enum TimerCommand {
+    Toggle,
+    Start,
+    Stop,
+    Skip,
+    Reset,
+    Extend(u32),
+    /// Çalışma oturumundaysa kısa molaya geç ve molayı başlat
+    StartBreak,
+}
+
+impl TimerCommand {
+    /// Komutu frontend'deki timer store'una ilet; sayfa henüz hazır değilse
+    /// (ilk yükleme, yeniden yükleme veya çökme) paylaşılan duruma uygula ve
+    /// hazır olduğunda iletilmek üzere sıraya al
+    pub fn dispatch(self, app_handle: &AppHandle) {
+        let command = match app_handle.try_state::<Dispatcher>() {
+            Some(dispatcher) => {
+                let shared_state = app_handle.try_state::<Arc<SharedTimerState>>();
+                dispatcher.enqueue(self, shared_state.as_deref().map(|s| s.as_ref()))
+            }
+            None => Some(self),
+        };
+        if let Some(command) = command {
+            command.emit(app_handle);
+        }
+    }
+
+    /// Toggle'ı mevcut duruma göre Start/Stop'a çevir. Sıradaki komutlar kaydedilmiş
+    /// durumla yeniden açılan sayfaya iletildiğinden, Toggle olarak kalsa timer'ı
+    /// kullanıcının gördüğünün tersine çevirebilirdi. Durum bilinmiyorsa (ilk
+    /// yükleme, ör. `--spawn`) Toggle, sayfanın geri yüklediği durumu çevirir.
+    fn resolve(self, status: Option<&TimerStatus>) -> TimerCommand {
+        match (self, status) {
+            (TimerCommand::Toggle, Some(status)) if status.is_active => TimerCommand::Stop,
+            (TimerCommand::Toggle, Some(_)) => TimerCommand::Start,
+            (command, _) => command,
+        }
+    }
+
+    /// Komutun timer durumuna etkisi. Mod değiştiren komutlar (Skip, StartBreak)
+    /// mod sürelerini bilen frontend'e bırakılır.
+    fn apply(self, status: &TimerStatus) -> Option<TimerStatus> {
+        let mut next = status.clone();
+        match self {
+            TimerCommand::Toggle => next.is_active = !next.is_active,
+            TimerCommand::Start => next.is_active = true,
+            TimerCommand::Stop => next.is_active = false,
+            TimerCommand::Reset => {
+                next.is_active = false;
+                next.time_left = next.duration;
+            }
+            TimerCommand::Extend(seconds) => {
+                next.time_left = next.time_left.saturating_add(seconds)
+            }
+            TimerCommand::Skip | TimerCommand::StartBreak => return None,
+        }
+        Some(next)
+    }
+
+    fn emit(self, app_handle: &AppHandle) {
+        if let Err(e) = app_handle.emit("timer-command", self) {
+            eprintln!("Timer komutu iletilemedi ({:?}): {}", self, e);
+        }
+    }
+}
+
+/// Frontend'in timer komutlarını dinleyip dinlemediğini izler
+pub struct Dispatcher {
+    ready: AtomicBool,
+    pending: Mutex<Vec<TimerCommand>>,
+}
+
+impl Dispatcher {
+    pub fn new() -> Self {
+        Self {
+            ready: AtomicBool::new(false),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Frontend hazır değilken gelen komut. Frontend daha önce durum gönderdiyse
+    /// (yeniden yükleme, çökme) komut hemen paylaşılan duruma uygulanır; tray,
+    /// D-Bus, MPRIS ve soket sonucu sayfa dönmeden görür.
+    ///
+    /// Frontend hazırsa komut olduğu gibi geri döner ve hemen iletilmelidir. `ready`
+    /// kuyruk kilidi altında okunur; `set_ready` ile aynı anda çalışırsa komut
+    /// boşaltılmış kuyrukta kalmaz.
+    fn enqueue(&self, command: TimerCommand, shared_state: Option<&SharedTimerState>) -> Option<TimerCommand> {
+        let mut pending = match self.pending.lock() {
+            Ok(pending) => pending,
+            Err(e) => {
+                eprintln!("Dispatcher::enqueue mutex poisoned: {}", e);
+                return None;
+            }
+        };
+        if self.ready.load(Ordering::SeqCst) {
+            return Some(command);
+        }
+
+        let status = shared_state.filter(|s| s.is_synced()).map(|s| s.snapshot());
+        let command = command.resolve(status.as_ref());
+        if let (Some(shared_state), Some(status)) = (shared_state, status) {
+            if let Some(next) = command.apply(&status) {
+                shared_state.update(next, None);
+            }
+        }
+
+        if pending.len() >= MAX_PENDING {
+            pending.remove(0);
+        }
+        pending.push(command);
+        None
+    }
+
+    /// Frontend dinleyicilerini kurdu: bekleyen komutları sırayla ilet
+    pub fn set_ready(&self, app_handle: &AppHandle) {
+        self.release_pending(|command| command.emit(app_handle));
+    }
+
+    /// Hazır durumuna geç ve bekleyen komutları ver. Kilit bırakılmadan iletilir;
+    /// bu arada gelen komutlar bekleyenlerden önce iletilmez.
+    fn release_pending(&self, deliver: impl FnMut(TimerCommand)) {
+        match self.pending.lock() {
+            Ok(mut pending) => {
+                self.ready.store(true, Ordering::SeqCst);
+                pending.drain(..).for_each(deliver);
+            }
+            Err(e) => eprintln!("Dispatcher::set_ready mutex poisoned: {}", e),
+        }
+    }
+
+    /// Sayfa yeniden yükleniyor: dinleyiciler kurulana kadar komutları beklet
+    pub fn set_not_ready(&self) {
+        self.ready.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Ana pencereyi göster ve öne getir (kısayol, ikinci örnek, D-Bus)
+pub fn show_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct VolumeChanged {
+    channel: Channel,
+    volume: f32,
+}
+
+/// Harici bir yüzeyden kanal ses düzeyini ayarla ve frontend ayarlarını güncelle
+pub fn set_volume(app_handle: &AppHandle, channel: Channel, volume: f32) {
+    let volume = volume.clamp(0.0, 1.0);
+    app_handle.state::<AudioPlayer>().set_volume(channel, volume);
+    if let Err(e) = app_handle.emit("volume-changed", VolumeChanged { channel, volume }) {
+        eprintln!("Ses düzeyi değişikliği iletilemedi: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take_pending(dispatcher: &Dispatcher) -> Vec<TimerCommand> {
+        let mut commands = Vec::new();
+        dispatcher.release_pending(|command| commands.push(command));
+        commands
+    }
+
+    fn synced(is_active: bool) -> SharedTimerState {
+        let state = SharedTimerState::new();
+        state.update(
+            TimerStatus {
+                is_active,
+                time_left: 600,
+                ..TimerStatus::default()
+            },
+            None,
+        );
+        state
+    }
+
+    #[test]
+    fn toggle_resolves_against_current_state() {
+        let running = TimerStatus {
+            is_active: true,
+            ..TimerStatus::default()
+        };
+        assert_eq!(TimerCommand::Toggle.resolve(Some(&running)), TimerCommand::Stop);
+        assert_eq!(
+            TimerCommand::Toggle.resolve(Some(&TimerStatus::default())),
+            TimerCommand::Start
+        );
+        assert_eq!(TimerCommand::Toggle.resolve(None), TimerCommand::Toggle);
+        assert_eq!(TimerCommand::Skip.resolve(Some(&running)), TimerCommand::Skip);
+    }
+
+    #[test]
+    fn apply_updates_status() {
+        let status = TimerStatus {
+            time_left: 100,
+            is_active: true,
+            ..TimerStatus::default()
+        };
+        let next = TimerCommand::Extend(60).apply(&status).unwrap();
+        assert_eq!(next.time_left, 160);
+        let next = TimerCommand::Reset.apply(&status).unwrap();
+        assert_eq!((next.time_left, next.is_active), (next.duration, false));
+        assert!(!TimerCommand::Stop.apply(&status).unwrap().is_active);
+        assert!(TimerCommand::Skip.apply(&status).is_none());
+    }
+
+    #[test]
+    fn queued_toggles_are_replayed_as_start_and_stop() {
+        let dispatcher = Dispatcher::new();
+        let state = synced(true);
+
+        dispatcher.enqueue(TimerCommand::Toggle, Some(&state));
+        assert!(!state.snapshot().is_active);
+        dispatcher.enqueue(TimerCommand::Toggle, Some(&state));
+        assert!(state.snapshot().is_active);
+        dispatcher.enqueue(TimerCommand::Extend(30), Some(&state));
+        assert_eq!(state.snapshot().time_left, 630);
+
+        assert_eq!(
+            take_pending(&dispatcher),
+            vec![TimerCommand::Stop, TimerCommand::Start, TimerCommand::Extend(30)]
+        );
+        assert!(dispatcher.ready.load(Ordering::SeqCst));
+        assert!(take_pending(&dispatcher).is_empty());
+    }
+
+    #[test]
+    fn toggle_before_first_sync_is_kept() {
+        let dispatcher = Dispatcher::new();
+        let state = SharedTimerState::new();
+
+        dispatcher.enqueue(TimerCommand::Toggle, Some(&state));
+        dispatcher.enqueue(TimerCommand::Start, Some(&state));
+        // Senkronize olmamış durum değiştirilmez
+        assert!(!state.is_synced());
+        assert_eq!(
+            take_pending(&dispatcher),
+            vec![TimerCommand::Toggle, TimerCommand::Start]
+        );
+    }
+
+    #[test]
+    fn queue_keeps_the_newest_commands() {
+        let dispatcher = Dispatcher::new();
+        for seconds in 0..(MAX_PENDING as u32 + 4) {
+            dispatcher.enqueue(TimerCommand::Extend(seconds), None);
+        }
+        let pending = take_pending(&dispatcher);
+        assert_eq!(pending.len(), MAX_PENDING);
+        assert_eq!(pending[0], TimerCommand::Extend(4));
+    }
+
+    #[test]
+    fn commands_after_ready_are_not_queued() {
+        let dispatcher = Dispatcher::new();
+        let state = synced(true);
+
+        // set_ready kuyruğu boşalttıktan sonra gelen komut iletilmek üzere geri döner
+        assert!(take_pending(&dispatcher).is_empty());
+        assert_eq!(
+            dispatcher.enqueue(TimerCommand::Toggle, Some(&state)),
+            Some(TimerCommand::Toggle)
+        );
+        // Frontend uygulayacağı için paylaşılan duruma dokunulmaz
+        assert!(state.snapshot().is_active);
+        assert!(dispatcher.pending.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+//! Oturum bitişi öncesi sesli uyarılar.
+//! Kalan süre uyarı eşiğine indiğinde yumuşak bir çan, son saniyelerde geri sayım
+//! tıkları çalınır. Zamanlama Rust tarafında oturumun bitiş anına göre yapılır ve
+//! her timer olayında yeniden hesaplanır; her işaret ayrıca D-Bus'ta
+//! Warning/Countdown sinyali olarak yayınlanır (bkz. dbus::forward_cues).
+
+use crate::audio::{AudioPlayer, Channel};
+use crate::dbus::{SharedTimerState, TimerStatus};
+use crate::sounds::{SoundEvent, SoundLibrary};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::Notify;
+use tokio::time::{Duration, Instant};
+
+/// Ses paketi uyarı sesi tanımlamamışsa çalınan çan
+const CHIME_HZ: u32 = 880;
+const CHIME_MS: u32 = 600;
+const COUNTDOWN_HZ: u32 = 1320;
+const COUNTDOWN_MS: u32 = 80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Cue {
+    /// Bitişe `seconds_left` saniye kala uyarı
+    Warning { seconds_left: u32 },
+    /// Son saniyelerde her saniye bir tık
+    Countdown { seconds_left: u32 },
+}
+
+impl Cue {
+    fn seconds_left(self) -> u32 {
+        match self {
+            Cue::Warning { seconds_left } | Cue::Countdown { seconds_left } => seconds_left,
+        }
+    }
+}
+
+/// Oturumun bitiş anına göre sıradaki işaret
+fn next_cue(end: Instant, warning_secs: u32, countdown_secs: u32) -> Option<(Instant, Cue)> {
+    let now = Instant::now();
+    let warning = (warning_secs > countdown_secs)
+        .then_some(Cue::Warning {
+            seconds_left: warning_secs,
+        })
+        .into_iter();
+    let countdown = (1..=countdown_secs).rev().map(|s| Cue::Countdown { seconds_left: s });
+
+    warning
+        .chain(countdown)
+        .filter_map(|cue| {
+            let at = end.checked_sub(Duration::from_secs(cue.seconds_left() as u64))?;
+            (at > now).then_some((at, cue))
+        })
+        .next()
+}
+
+/// Aktif oturumun bitiş anı
+fn session_end(status: &TimerStatus) -> Option<Instant> {
+    status
+        .is_active
+        .then(|| Instant::now() + Duration::from_secs(status.time_left as u64))
+}
+
+pub struct CueScheduler {
+    warning_secs: Arc<AtomicU32>,
+    countdown_secs: Arc<AtomicU32>,
+    /// Süreler değişti; sıradaki işaret yeniden hesaplanır
+    changed: Arc<Notify>,
+    cues: broadcast::Sender<Cue>,
+}
+
+impl CueScheduler {
+    pub fn new() -> Self {
+        let (cues, _) = broadcast::channel(16);
+        Self {
+            warning_secs: Arc::new(AtomicU32::new(0)),
+            countdown_secs: Arc::new(AtomicU32::new(0)),
+            changed: Arc::new(Notify::new()),
+            cues,
+        }
+    }
+
+    /// Uyarı çanı bitişten kaç saniye önce çalsın (0 = kapalı)
+    pub fn set_warning_seconds(&self, seconds: u32) {
+        if self.warning_secs.swap(seconds, Ordering::SeqCst) != seconds {
+            self.changed.notify_one();
+        }
+    }
+
+    /// Son kaç saniyede geri sayım tıkı çalsın (0 = kapalı)
+    pub fn set_countdown_seconds(&self, seconds: u32) {
+        if self.countdown_secs.swap(seconds, Ordering::SeqCst) != seconds {
+            self.changed.notify_one();
+        }
+    }
+
+    /// Çalınan işaretlere abone ol
+    pub fn subscribe(&self) -> broadcast::Receiver<Cue> {
+        self.cues.subscribe()
+    }
+
+    /// Arka plan görevi olarak işaretleri zamanla
+    pub fn start(&self, app_handle: AppHandle, shared_state: Arc<SharedTimerState>) {
+        let warning_secs = self.warning_secs.clone();
+        let countdown_secs = self.countdown_secs.clone();
+        let changed = self.changed.clone();
+        let cues = self.cues.clone();
+        let mut events = shared_state.subscribe();
+
+        tauri::async_runtime::spawn(async move {
+            let mut end: Option<Instant> = None;
+
+            loop {
+                let next = end.and_then(|end| {
+                    next_cue(
+                        end,
+                        warning_secs.load(Ordering::SeqCst),
+                        countdown_secs.load(Ordering::SeqCst),
+                    )
+                });
+                let due = async {
+                    match next {
+                        Some((at, _)) => tokio::time::sleep_until(at).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                };
+
+                tokio::select! {
+                    received = events.recv() => match received {
+                        // Başlatma, duraklatma, uzatma vb. bitiş anını değiştirir
+                        Ok(transition) => end = session_end(&transition.status),
+                        Err(RecvError::Lagged(_)) => end = session_end(&shared_state.snapshot()),
+                        Err(RecvError::Closed) => break,
+                    },
+                    // Bitiş anı aynı kalır; döngü başında yeni sürelerle hesaplanır
+                    _ = changed.notified() => continue,
+                    _ = due => {
+                        let Some((_, cue)) = next else { continue };
+                        if shared_state.snapshot().is_active {
+                            // Ses thread'inin yanıtı beklenirken zamanlama döngüsü beklemesin
+                            let app_handle = app_handle.clone();
+                            tauri::async_runtime::spawn(async move { play(&app_handle, cue).await });
+                            // Dinleyici yoksa gönderim hata döner, bu normaldir
+                            let _ = cues.send(cue);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+async fn play(app_handle: &AppHandle, cue: Cue) {
+    let player = app_handle.state::<AudioPlayer>();
+    let result = match cue {
+        Cue::Warning { .. } => {
+            // Seçili ses paketinin uyarı sesi, yoksa üretilen çan
+            let pack_sound = app_handle
+                .try_state::<SoundLibrary>()
+                .and_then(|library| library.resolve(SoundEvent::Warning));
+            match pack_sound {
+                Some(path) => player.play(Channel::Alerts, &path, false, 0).await,
+                None => player.play_tone(CHIME_HZ, CHIME_MS).await,
+            }
+        }
+        Cue::Countdown { .. } => player.play_tone(COUNTDOWN_HZ, COUNTDOWN_MS).await,
+    };
+    if let Err(e) = result {
+        eprintln!("Uyarı sesi çalınamadı: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sınırda zamanlama farkı olmasın diye bitiş yarım saniye ötede
+    fn end_in(secs: u64) -> Instant {
+        Instant::now() + Duration::from_secs(secs) + Duration::from_millis(500)
+    }
+
+    fn cue(end: Instant, warning_secs: u32, countdown_secs: u32) -> Option<Cue> {
+        next_cue(end, warning_secs, countdown_secs).map(|(_, cue)| cue)
+    }
+
+    #[test]
+    fn warning_comes_before_countdown() {
+        let end = end_in(60);
+        let (at, cue) = next_cue(end, 30, 5).unwrap();
+        assert_eq!(cue, Cue::Warning { seconds_left: 30 });
+        assert_eq!(at, end - Duration::from_secs(30));
+    }
+
+    #[test]
+    fn countdown_boundaries() {
+        // İlk tık geri sayım süresinin başında
+        assert_eq!(cue(end_in(10), 0, 3), Some(Cue::Countdown { seconds_left: 3 }));
+        // Geçen tıklar atlanır
+        assert_eq!(cue(end_in(2), 0, 3), Some(Cue::Countdown { seconds_left: 2 }));
+        assert_eq!(cue(end_in(1), 0, 3), Some(Cue::Countdown { seconds_left: 1 }));
+        // Son tık da geçtiyse işaret yok; bitiş anında tık çalınmaz
+        assert_eq!(cue(end_in(0), 0, 3), None);
+    }
+
+    #[test]
+    fn zero_warning_is_disabled() {
+        assert_eq!(cue(end_in(60), 0, 0), None);
+        assert_eq!(cue(end_in(60), 0, 5), Some(Cue::Countdown { seconds_left: 5 }));
+    }
+
+    #[test]
+    fn warning_inside_countdown_is_skipped() {
+        assert_eq!(cue(end_in(60), 3, 5), Some(Cue::Countdown { seconds_left: 5 }));
+        assert_eq!(cue(end_in(60), 5, 5), Some(Cue::Countdown { seconds_left: 5 }));
+    }
+
+    #[test]
+    fn warning_later_than_remaining_time_is_skipped() {
+        assert_eq!(cue(end_in(10), 30, 3), Some(Cue::Countdown { seconds_left: 3 }));
+        assert_eq!(cue(end_in(10), 30, 0), None);
+    }
+}
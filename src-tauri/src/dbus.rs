@@ -1,14 +1,23 @@
+use crate::audio::{AudioPlayer, Channel};
+use crate::control::{self, TimerCommand};
+use crate::cues::Cue;
+use crate::events::{TimerEvent, TimerTransition, TransitionReason};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter};
-use zbus::{connection, interface};
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::{self, error::RecvError};
+use zbus::{connection, interface, Connection, SignalContext};
 
 /// Frontend'den güncellenen timer durumu
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TimerStatus {
     pub state: String,
     pub time_left: u32,
     pub is_active: bool,
     pub sessions_completed: u32,
+    /// Mevcut modun planlanan süresi (saniye)
+    pub duration: u32,
 }
 
 impl Default for TimerStatus {
@@ -18,6 +27,7 @@ impl Default for TimerStatus {
             time_left: 25 * 60,
             is_active: false,
             sessions_completed: 0,
+            duration: 25 * 60,
         }
     }
 }
@@ -25,28 +35,68 @@ impl Default for TimerStatus {
 /// Rust ve D-Bus arasında paylaşılan durum
 pub struct SharedTimerState {
     pub status: Mutex<TimerStatus>,
+    /// Frontend'den ilk durum geldi mi (ilk güncelleme olay üretmez)
+    synced: AtomicBool,
+    events: broadcast::Sender<TimerTransition>,
 }
 
 impl SharedTimerState {
     pub fn new() -> Self {
+        let (events, _) = broadcast::channel(32);
         Self {
             status: Mutex::new(TimerStatus::default()),
+            synced: AtomicBool::new(false),
+            events,
         }
     }
 
-    pub fn update(&self, state: String, time_left: u32, is_active: bool, sessions_completed: u32) {
-        match self.status.lock() {
-            Ok(mut s) => {
-                s.state = state;
-                s.time_left = time_left;
-                s.is_active = is_active;
-                s.sessions_completed = sessions_completed;
+    /// `reason`: mod değiştiyse oturumun bitip bitmediği (bkz. TransitionReason)
+    pub fn update(&self, next: TimerStatus, reason: Option<TransitionReason>) {
+        let prev = match self.status.lock() {
+            Ok(mut s) => std::mem::replace(&mut *s, next.clone()),
+            Err(e) => {
+                eprintln!("SharedTimerState::update mutex poisoned: {}", e);
+                return;
             }
-            Err(e) => eprintln!("SharedTimerState::update mutex poisoned: {}", e),
+        };
+
+        if !self.synced.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        for event in TimerEvent::detect(&prev, &next, reason) {
+            // Dinleyici yoksa gönderim hata döner, bu normaldir
+            let _ = self.events.send(TimerTransition {
+                event,
+                status: next.clone(),
+            });
         }
     }
+
+    /// Frontend en az bir kez durum gönderdi mi
+    pub fn is_synced(&self) -> bool {
+        self.synced.load(Ordering::SeqCst)
+    }
+
+    /// Mevcut durumun kopyası
+    pub fn snapshot(&self) -> TimerStatus {
+        self.status
+            .lock()
+            .map(|s| s.clone())
+            .unwrap_or_else(|e| {
+                eprintln!("SharedTimerState::snapshot mutex poisoned: {}", e);
+                TimerStatus::default()
+            })
+    }
+
+    /// Timer olaylarına abone ol
+    pub fn subscribe(&self) -> broadcast::Receiver<TimerTransition> {
+        self.events.subscribe()
+    }
 }
 
+const SERVICE_PATH: &str = "/com/osmandulundu/pomodoro";
+
 /// D-Bus servisi: com.osmandulundu.Pomodoro
 /// Harici araçlar (waybar, polybar, scriptler) bu arayüz üzerinden
 /// timer'ı kontrol edebilir ve durumunu sorgulayabilir.
@@ -59,34 +109,63 @@ struct PomodoroService {
 impl PomodoroService {
     /// Timer'ı başlat veya duraklat (toggle)
     async fn toggle(&self) {
-        let _ = self.app_handle.emit("dbus-toggle", ());
+        TimerCommand::Toggle.dispatch(&self.app_handle);
     }
 
     /// Timer çalışmıyorsa başlat
     async fn start(&self) {
-        let _ = self.app_handle.emit("dbus-start", ());
+        TimerCommand::Start.dispatch(&self.app_handle);
     }
 
     /// Timer çalışıyorsa durdur
     async fn stop(&self) {
-        let _ = self.app_handle.emit("dbus-stop", ());
+        TimerCommand::Stop.dispatch(&self.app_handle);
     }
 
     /// Mevcut oturumu atla
     async fn skip(&self) {
-        let _ = self.app_handle.emit("dbus-skip", ());
+        TimerCommand::Skip.dispatch(&self.app_handle);
     }
 
     /// Timer'ı sıfırla
     async fn reset(&self) {
-        let _ = self.app_handle.emit("dbus-reset", ());
+        TimerCommand::Reset.dispatch(&self.app_handle);
     }
 
     /// Timer'a saniye ekle
     async fn extend(&self, seconds: u32) {
-        let _ = self.app_handle.emit("dbus-extend", seconds);
+        TimerCommand::Extend(seconds).dispatch(&self.app_handle);
     }
 
+    /// Ana pencereyi göster ve öne getir (uygulama ikinci kez başlatıldığında)
+    async fn show_window(&self) {
+        control::show_window(&self.app_handle);
+    }
+
+    /// Kanal ses düzeyini ayarla (ticking, ambient, alerts; 0.0 - 1.0)
+    async fn set_volume(&self, channel: String, volume: f64) -> zbus::fdo::Result<()> {
+        let channel = Channel::from_name(&channel)
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("Unknown channel: {}", channel)))?;
+        control::set_volume(&self.app_handle, channel, volume as f32);
+        Ok(())
+    }
+
+    /// Kanal ses düzeyini oku
+    async fn get_volume(&self, channel: String) -> zbus::fdo::Result<f64> {
+        let channel = Channel::from_name(&channel)
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("Unknown channel: {}", channel)))?;
+        let volumes = self.app_handle.state::<AudioPlayer>().volumes().await;
+        Ok(volumes.get(&channel).copied().unwrap_or(1.0) as f64)
+    }
+
+    /// Bitiş öncesi uyarı çanı çaldı
+    #[zbus(signal)]
+    async fn warning(ctxt: &SignalContext<'_>, seconds_left: u32) -> zbus::Result<()>;
+
+    /// Geri sayım tıkı çaldı
+    #[zbus(signal)]
+    async fn countdown(ctxt: &SignalContext<'_>, seconds_left: u32) -> zbus::Result<()>;
+
     /// Mevcut mod (work, shortBreak, longBreak)
     #[zbus(property)]
     async fn state(&self) -> String {
@@ -140,6 +219,37 @@ impl PomodoroService {
     }
 }
 
+/// Çalınan uyarı seslerini Warning/Countdown sinyali olarak yayınla
+pub fn forward_cues(conn: &Connection, mut cues: broadcast::Receiver<Cue>) {
+    let conn = conn.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let cue = match cues.recv().await {
+                Ok(c) => c,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+
+            let ctxt = match SignalContext::new(&conn, SERVICE_PATH) {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("D-Bus sinyal bağlamı oluşturulamadı: {}", e);
+                    continue;
+                }
+            };
+            let result = match cue {
+                Cue::Warning { seconds_left } => PomodoroService::warning(&ctxt, seconds_left).await,
+                Cue::Countdown { seconds_left } => {
+                    PomodoroService::countdown(&ctxt, seconds_left).await
+                }
+            };
+            if let Err(e) = result {
+                eprintln!("D-Bus sinyali gönderilemedi: {}", e);
+            }
+        }
+    });
+}
+
 /// D-Bus servisini başlat (tokio async task olarak)
 pub async fn start_dbus_service(
     app_handle: AppHandle,
@@ -152,7 +262,7 @@ pub async fn start_dbus_service(
 
     let conn = connection::Builder::session()?
         .name("com.osmandulundu.pomodoro")?
-        .serve_at("/com/osmandulundu/pomodoro", service)?
+        .serve_at(SERVICE_PATH, service)?
         .build()
         .await?;
 
@@ -0,0 +1,181 @@
+//! GTK/GDK ortam değişkenlerinin seçimi.
+//! GDK arka ucu sırasıyla `--backend` bayrağından, kullanıcının kendi
+//! GDK_BACKEND değişkeninden, yapılandırma dosyasından (`display.backend`) ve
+//! oturum türünden belirlenir. GTK_CSD=0 yalnızca sunucu tarafı süslemelerin
+//! gerçekten kullanılabildiği durumlarda (X11 veya KDE Wayland) ve kullanıcı
+//! kendisi ayarlamamışsa verilir. GTK başlamadan, main() içinde çağrılmalıdır.
+
+use crate::config::AppConfig;
+use clap::ValueEnum;
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// Oturum türüne göre seç (Wayland oturumunda Wayland, aksi halde X11)
+    #[default]
+    Auto,
+    Wayland,
+    X11,
+}
+
+/// Wayland oturumunda mı çalışıyoruz (GDK_BACKEND=x11 olsa bile)
+pub fn is_wayland_session() -> bool {
+    std::env::var("XDG_SESSION_TYPE").is_ok_and(|t| t == "wayland")
+        || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+fn is_kde() -> bool {
+    std::env::var("XDG_CURRENT_DESKTOP").is_ok_and(|d| d.split(':').any(|d| d == "KDE"))
+}
+
+/// Seçim için okunan ortam değişkenleri
+struct Environment {
+    gdk_backend: Option<String>,
+    gtk_csd: bool,
+    wayland_session: bool,
+    kde: bool,
+}
+
+impl Environment {
+    fn current() -> Self {
+        Self {
+            gdk_backend: std::env::var("GDK_BACKEND").ok(),
+            gtk_csd: std::env::var_os("GTK_CSD").is_some(),
+            wayland_session: is_wayland_session(),
+            kde: is_kde(),
+        }
+    }
+}
+
+/// Ayarlanacak değişkenler (None/false: dokunulmaz)
+#[derive(Debug, PartialEq, Eq)]
+struct Variables {
+    gdk_backend: Option<&'static str>,
+    disable_csd: bool,
+}
+
+/// `config` yalnızca bayrak ve GDK_BACKEND yoksa okunur
+fn choose(flag: Option<Backend>, env: &Environment, config: impl FnOnce() -> Backend) -> Variables {
+    let backend = match flag {
+        Some(backend) => Some(backend),
+        // Kullanıcının kendi ayarı yapılandırma dosyasından önce gelir
+        None if env.gdk_backend.is_some() => None,
+        None => Some(config()),
+    };
+
+    let native_wayland = match backend {
+        Some(Backend::Auto) => env.wayland_session,
+        Some(Backend::Wayland) => true,
+        Some(Backend::X11) => false,
+        None => env
+            .gdk_backend
+            .as_deref()
+            .is_some_and(|b| b.starts_with("wayland")),
+    };
+
+    Variables {
+        // Wayland başarısız olursa GDK X11'e (XWayland) düşer
+        gdk_backend: backend.map(|_| if native_wayland { "wayland,x11" } else { "x11" }),
+        // Sunucu tarafı süslemeler (SSD): X11'de ve KDE Wayland'de çalışır; GNOME
+        // Wayland'de CSD kapatılırsa pencere başlıksız kalır
+        disable_csd: !env.gtk_csd && (!native_wayland || env.kde),
+    }
+}
+
+/// GDK_BACKEND ve GTK_CSD değişkenlerini ayarla
+pub fn configure(flag: Option<Backend>) {
+    let variables = choose(flag, &Environment::current(), || {
+        AppConfig::load().display.backend
+    });
+    if let Some(value) = variables.gdk_backend {
+        std::env::set_var("GDK_BACKEND", value);
+    }
+    if variables.disable_csd {
+        std::env::set_var("GTK_CSD", "0");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(gdk_backend: Option<&str>, wayland_session: bool, kde: bool) -> Environment {
+        Environment {
+            gdk_backend: gdk_backend.map(str::to_string),
+            gtk_csd: false,
+            wayland_session,
+            kde,
+        }
+    }
+
+    fn unused() -> Backend {
+        panic!("yapılandırma okunmamalı")
+    }
+
+    #[test]
+    fn flag_wins_over_environment_and_config() {
+        let v = choose(
+            Some(Backend::X11),
+            &env(Some("wayland"), true, false),
+            unused,
+        );
+        assert_eq!(v.gdk_backend, Some("x11"));
+        assert!(v.disable_csd);
+
+        let v = choose(Some(Backend::Wayland), &env(None, false, false), unused);
+        assert_eq!(v.gdk_backend, Some("wayland,x11"));
+        assert!(!v.disable_csd);
+    }
+
+    #[test]
+    fn user_gdk_backend_is_kept() {
+        let v = choose(None, &env(Some("wayland"), true, false), unused);
+        assert_eq!(
+            v,
+            Variables {
+                gdk_backend: None,
+                disable_csd: false
+            }
+        );
+
+        let v = choose(None, &env(Some("x11"), true, false), unused);
+        assert_eq!(
+            v,
+            Variables {
+                gdk_backend: None,
+                disable_csd: true
+            }
+        );
+    }
+
+    #[test]
+    fn config_and_session_type_decide_otherwise() {
+        let v = choose(None, &env(None, false, false), || Backend::Auto);
+        assert_eq!(v.gdk_backend, Some("x11"));
+
+        let v = choose(None, &env(None, true, false), || Backend::Auto);
+        assert_eq!(
+            v,
+            Variables {
+                gdk_backend: Some("wayland,x11"),
+                disable_csd: false
+            }
+        );
+
+        let v = choose(None, &env(None, true, false), || Backend::X11);
+        assert_eq!(v.gdk_backend, Some("x11"));
+    }
+
+    #[test]
+    fn csd_is_disabled_only_where_ssd_works() {
+        // KDE Wayland sunucu tarafı süslemeleri destekler
+        let v = choose(Some(Backend::Auto), &env(None, true, true), unused);
+        assert!(v.disable_csd);
+
+        // Kullanıcının GTK_CSD ayarına dokunulmaz
+        let mut environment = env(None, false, false);
+        environment.gtk_csd = true;
+        assert!(!choose(Some(Backend::X11), &environment, unused).disable_csd);
+    }
+}
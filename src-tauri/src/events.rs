@@ -0,0 +1,195 @@
+use crate::dbus::TimerStatus;
+use serde::{Deserialize, Serialize};
+
+/// Timer durum değişikliklerinden türetilen olaylar.
+/// Timer motoru frontend'de çalıştığı için olaylar, `update_timer_status` ile
+/// gelen ardışık durumların karşılaştırılmasıyla elde edilir.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum TimerEvent {
+    /// Yeni bir oturum baştan başladı
+    Started,
+    /// Duraklatılmış oturum devam etti
+    Resumed,
+    /// Aktif oturum duraklatıldı
+    Paused,
+    /// Oturuma süre eklendi
+    Extended { seconds: u32 },
+    /// Oturum sıfırlandı
+    Reset,
+    /// Oturum süresi dolarak bitti
+    Finished { from: String, to: String },
+    /// Oturum bitmeden sonraki moda geçildi
+    Skipped { from: String, to: String },
+}
+
+/// Mod değişikliğinin nedeni; frontend `update_timer_status` ile bildirir
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransitionReason {
+    /// Oturum süresi doldu
+    Finished,
+    /// Kullanıcı atladı veya modu elle değiştirdi
+    Skipped,
+}
+
+/// Olay ve olay anındaki timer durumu
+#[derive(Clone, Debug, Serialize)]
+pub struct TimerTransition {
+    pub event: TimerEvent,
+    pub status: TimerStatus,
+}
+
+impl TimerEvent {
+    /// Olayın adı (JSON'daki `kind` alanıyla aynı)
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimerEvent::Started => "started",
+            TimerEvent::Resumed => "resumed",
+            TimerEvent::Paused => "paused",
+            TimerEvent::Extended { .. } => "extended",
+            TimerEvent::Reset => "reset",
+            TimerEvent::Finished { .. } => "finished",
+            TimerEvent::Skipped { .. } => "skipped",
+        }
+    }
+
+    /// İki ardışık durum arasındaki olayları çıkar.
+    /// Mod değiştiğinde bitiş/atlama ayrımı frontend'in bildirdiği nedene göre
+    /// yapılır; neden gelmezse geçiş atlama sayılır.
+    pub fn detect(
+        prev: &TimerStatus,
+        next: &TimerStatus,
+        reason: Option<TransitionReason>,
+    ) -> Vec<TimerEvent> {
+        let mut events = Vec::new();
+
+        if prev.state != next.state {
+            let (from, to) = (prev.state.clone(), next.state.clone());
+            events.push(match reason {
+                Some(TransitionReason::Finished) => TimerEvent::Finished { from, to },
+                Some(TransitionReason::Skipped) | None => TimerEvent::Skipped { from, to },
+            });
+            // Otomatik başlatma açıksa yeni oturum hemen başlar
+            if next.is_active {
+                events.push(TimerEvent::Started);
+            }
+            return events;
+        }
+
+        match (prev.is_active, next.is_active) {
+            (false, true) if next.time_left >= next.duration => events.push(TimerEvent::Started),
+            (false, true) => events.push(TimerEvent::Resumed),
+            (true, false) if next.time_left == next.duration => events.push(TimerEvent::Reset),
+            (true, false) => events.push(TimerEvent::Paused),
+            (false, false)
+                if next.time_left == next.duration && prev.time_left != next.time_left =>
+            {
+                events.push(TimerEvent::Reset)
+            }
+            _ if next.time_left > prev.time_left => events.push(TimerEvent::Extended {
+                seconds: next.time_left - prev.time_left,
+            }),
+            _ => {}
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(state: &str, time_left: u32, is_active: bool) -> TimerStatus {
+        TimerStatus {
+            state: state.to_string(),
+            time_left,
+            is_active,
+            sessions_completed: 0,
+            duration: 25 * 60,
+        }
+    }
+
+    #[test]
+    fn finished_uses_reported_reason() {
+        // Son saniyede atlama bitiş sayılmamalı
+        let events = TimerEvent::detect(
+            &status("work", 1, true),
+            &status("shortBreak", 300, false),
+            Some(TransitionReason::Skipped),
+        );
+        assert_eq!(
+            events,
+            vec![TimerEvent::Skipped {
+                from: "work".to_string(),
+                to: "shortBreak".to_string(),
+            }]
+        );
+
+        // Duraklatılmışken biten oturum da bitiş sayılır
+        let events = TimerEvent::detect(
+            &status("work", 10, false),
+            &status("shortBreak", 300, false),
+            Some(TransitionReason::Finished),
+        );
+        assert_eq!(
+            events,
+            vec![TimerEvent::Finished {
+                from: "work".to_string(),
+                to: "shortBreak".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn mode_change_without_reason_is_skip() {
+        let events = TimerEvent::detect(
+            &status("shortBreak", 100, true),
+            &status("work", 1500, false),
+            None,
+        );
+        assert_eq!(events.first().map(TimerEvent::name), Some("skipped"));
+    }
+
+    #[test]
+    fn auto_started_session_reports_started() {
+        let events = TimerEvent::detect(
+            &status("work", 1, true),
+            &status("shortBreak", 300, true),
+            Some(TransitionReason::Finished),
+        );
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1], TimerEvent::Started);
+    }
+
+    #[test]
+    fn start_pause_resume_and_reset() {
+        let idle = status("work", 1500, false);
+        let running = status("work", 1499, true);
+        let paused = status("work", 1400, false);
+
+        assert_eq!(
+            TimerEvent::detect(&idle, &status("work", 1500, true), None),
+            vec![TimerEvent::Started]
+        );
+        assert_eq!(
+            TimerEvent::detect(&running, &paused, None),
+            vec![TimerEvent::Paused]
+        );
+        assert_eq!(
+            TimerEvent::detect(&paused, &status("work", 1400, true), None),
+            vec![TimerEvent::Resumed]
+        );
+        assert_eq!(TimerEvent::detect(&paused, &idle, None), vec![TimerEvent::Reset]);
+        assert_eq!(TimerEvent::detect(&running, &idle, None), vec![TimerEvent::Reset]);
+    }
+
+    #[test]
+    fn extend_reports_added_seconds() {
+        assert_eq!(
+            TimerEvent::detect(&status("work", 100, true), &status("work", 160, true), None),
+            vec![TimerEvent::Extended { seconds: 60 }]
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Kullanıcı hook'ları.
+//! Timer olaylarında $XDG_CONFIG_HOME/pomodoro/hooks/ dizinindeki çalıştırılabilir
+//! dosyaları ve config.json'daki komutları çalıştırır. Olay bilgisi POMODORO_*
+//! ortam değişkenleriyle ve stdin'e yazılan JSON ile aktarılır.
+
+use crate::config::{self, HookConfig};
+use crate::dbus::SharedTimerState;
+use crate::events::{TimerEvent, TimerTransition};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Semaphore;
+
+/// Çalıştırılacak tek bir hook
+struct Hook {
+    program: PathBuf,
+    args: Vec<String>,
+}
+
+/// hooks/ dizinindeki çalıştırılabilir dosyaları ada göre sıralı listele
+fn hooks_in_dir(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut hooks: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            std::fs::metadata(path)
+                .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+                .unwrap_or(false)
+        })
+        .collect();
+    hooks.sort();
+    hooks
+}
+
+/// Olay için çalıştırılacak hook'ları topla
+fn hooks_for(config: &HookConfig, event: &TimerEvent) -> Vec<Hook> {
+    let mut hooks: Vec<Hook> = config::config_dir()
+        .map(|dir| hooks_in_dir(&dir.join("hooks")))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|program| Hook {
+            program,
+            args: Vec::new(),
+        })
+        .collect();
+
+    hooks.extend(
+        config
+            .commands
+            .iter()
+            .filter(|c| c.events.is_empty() || c.events.iter().any(|e| e == event.name()))
+            .map(|c| Hook {
+                program: c.command.clone(),
+                args: c.args.clone(),
+            }),
+    );
+    hooks
+}
+
+/// Olay bilgisini ortam değişkenleri olarak hazırla
+fn hook_env(transition: &TimerTransition) -> Vec<(&'static str, String)> {
+    let status = &transition.status;
+    let mut env = vec![
+        ("POMODORO_EVENT", transition.event.name().to_string()),
+        ("POMODORO_MODE", status.state.clone()),
+        ("POMODORO_TIME_LEFT", status.time_left.to_string()),
+        ("POMODORO_DURATION", status.duration.to_string()),
+        ("POMODORO_IS_ACTIVE", status.is_active.to_string()),
+        ("POMODORO_SESSIONS_COMPLETED", status.sessions_completed.to_string()),
+    ];
+
+    match &transition.event {
+        TimerEvent::Finished { from, to } | TimerEvent::Skipped { from, to } => {
+            env.push(("POMODORO_FROM", from.clone()));
+            env.push(("POMODORO_TO", to.clone()));
+        }
+        TimerEvent::Extended { seconds } => {
+            env.push(("POMODORO_EXTENDED_BY", seconds.to_string()));
+        }
+        _ => {}
+    }
+    env
+}
+
+async fn run_hook(hook: Hook, env: Vec<(&'static str, String)>, payload: String, timeout: Duration) {
+    let name = hook.program.display().to_string();
+
+    let mut child = match Command::new(&hook.program)
+        .args(&hook.args)
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Hook başlatılamadı ({}): {}", name, e);
+            return;
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // Betik stdin'i okumasa bile hata sayılmaz
+        let _ = stdin.write_all(payload.as_bytes()).await;
+    }
+
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(Ok(output)) if output.status.success() => {}
+        Ok(Ok(output)) => eprintln!(
+            "Hook başarısız oldu ({}, {}): {}",
+            name,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Ok(Err(e)) => eprintln!("Hook beklenirken hata ({}): {}", name, e),
+        // Zaman aşımında future düşer, kill_on_drop süreci sonlandırır
+        Err(_) => eprintln!("Hook zaman aşımına uğradı ({}, {:?})", name, timeout),
+    }
+}
+
+pub struct HookRunner {
+    config: Arc<HookConfig>,
+}
+
+impl HookRunner {
+    pub fn new(config: HookConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+
+    /// Arka plan görevi olarak timer olaylarını dinle ve hook'ları çalıştır
+    pub fn start(&self, shared_state: Arc<SharedTimerState>) {
+        let config = self.config.clone();
+        let mut events = shared_state.subscribe();
+        let limit = Arc::new(Semaphore::new(config.max_concurrent.max(1)));
+        let timeout = Duration::from_secs(config.timeout_secs.max(1));
+
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let transition = match events.recv().await {
+                    Ok(t) => t,
+                    Err(RecvError::Lagged(n)) => {
+                        eprintln!("Hook'lar {} olayı kaçırdı", n);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                let payload = match serde_json::to_string(&transition) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        eprintln!("Hook verisi hazırlanamadı: {}", e);
+                        continue;
+                    }
+                };
+
+                for hook in hooks_for(&config, &transition.event) {
+                    let env = hook_env(&transition);
+                    let payload = payload.clone();
+                    let limit = limit.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let Ok(_permit) = limit.acquire_owned().await else {
+                            return;
+                        };
+                        run_hook(hook, env, payload, timeout).await;
+                    });
+                }
+            }
+        });
+    }
+}
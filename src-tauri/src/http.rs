@@ -0,0 +1,368 @@
+//! Yerel HTTP/JSON API.
+//! D-Bus konuşamayan araçlar (tarayıcı eklentileri, SSH tüneli üzerinden editörler)
+//! için PomodoroService ile aynı komutları, istatistikleri ve bir SSE olay akışını
+//! sunar. Yalnızca 127.0.0.1 üzerinde dinler ve config.json'daki token'ı ister.
+//! CORS başlığı gönderilmez: web sayfaları yanıtları okuyamaz, host izni olan
+//! tarayıcı eklentileri ise CORS'tan etkilenmez.
+
+use crate::config::HttpConfig;
+use crate::control::TimerCommand;
+use crate::db::Database;
+use crate::dbus::SharedTimerState;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+
+const MAX_HEAD_BYTES: u64 = 8 * 1024;
+/// İstek satırı ve başlıklar bu süre içinde gelmezse bağlantı kapatılır
+const HEAD_TIMEOUT_SECS: u64 = 5;
+const KEEPALIVE_SECS: u64 = 30;
+
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+}
+
+enum Response {
+    Json(u16, String),
+    EventStream,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Response::Json(200, body)
+    }
+
+    fn error(code: u16, message: impl std::fmt::Display) -> Self {
+        Response::Json(code, serde_json::json!({ "error": message.to_string() }).to_string())
+    }
+}
+
+fn reason(code: u16) -> &'static str {
+    match code {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+/// %XX ve '+' kodlamalarını çöz
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = |b: u8| (b as char).to_digit(16);
+                match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(hi), Some(lo)) => {
+                        out.push((hi * 16 + lo) as u8);
+                        i += 3;
+                        continue;
+                    }
+                    _ => out.push(b'%'),
+                }
+            }
+            b'+' => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(k), percent_decode(v))
+        })
+        .collect()
+}
+
+/// İstek satırını ve başlıkları oku (gövde kullanılmaz).
+/// Toplam boyut MAX_HEAD_BYTES ile sınırlıdır; sınırı aşan istek reddedilir.
+async fn read_request<R: AsyncBufRead + Unpin>(reader: R) -> Option<Request> {
+    let mut reader = reader.take(MAX_HEAD_BYTES);
+    let mut line = String::new();
+
+    reader.read_line(&mut line).await.ok()?;
+    if !line.ends_with('\n') {
+        return None;
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?.to_string();
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).await.ok()?;
+        // Bağlantı kapandı veya boyut sınırına ulaşıldı
+        if !header.ends_with('\n') {
+            return None;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    Some(Request {
+        method,
+        path: path.to_string(),
+        query: parse_query(query),
+        headers,
+    })
+}
+
+/// Zamanlama saldırılarına karşı sabit süreli karşılaştırma
+fn token_matches(given: &str, expected: &str) -> bool {
+    let (a, b) = (given.as_bytes(), expected.as_bytes());
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn authorized(req: &Request, token: &str) -> bool {
+    let given = req
+        .headers
+        .get("authorization")
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .or_else(|| req.query.get("token").map(String::as_str));
+    given.is_some_and(|g| token_matches(g, token))
+}
+
+fn query_param<T: std::str::FromStr>(req: &Request, name: &str) -> Result<T, Response> {
+    req.query
+        .get(name)
+        .ok_or_else(|| Response::error(400, format!("Missing parameter: {}", name)))?
+        .parse()
+        .map_err(|_| Response::error(400, format!("Invalid parameter: {}", name)))
+}
+
+fn stats_response<T: serde::Serialize>(
+    result: Result<T, Box<dyn std::error::Error>>,
+) -> Response {
+    match result {
+        Ok(stats) => match serde_json::to_string(&stats) {
+            Ok(body) => Response::ok(body),
+            Err(e) => Response::error(500, e),
+        },
+        Err(e) => Response::error(400, e),
+    }
+}
+
+fn route(app: &AppHandle, req: &Request) -> Result<Response, Response> {
+    let command = match req.path.as_str() {
+        "/toggle" => Some(TimerCommand::Toggle),
+        "/start" => Some(TimerCommand::Start),
+        "/stop" => Some(TimerCommand::Stop),
+        "/skip" => Some(TimerCommand::Skip),
+        "/reset" => Some(TimerCommand::Reset),
+        "/extend" => {
+            let seconds = match req.query.get("seconds") {
+                Some(_) => query_param(req, "seconds")?,
+                None => 60,
+            };
+            Some(TimerCommand::Extend(seconds))
+        }
+        _ => None,
+    };
+
+    if let Some(command) = command {
+        if req.method != "POST" {
+            return Err(Response::error(405, "Use POST"));
+        }
+        command.dispatch(app);
+        return Ok(Response::ok(r#"{"ok":true}"#.to_string()));
+    }
+
+    if req.method != "GET" {
+        return Err(Response::error(405, "Use GET"));
+    }
+
+    let response = match req.path.as_str() {
+        "/status" => {
+            let status = app.state::<Arc<SharedTimerState>>().snapshot();
+            serde_json::to_string(&status)
+                .map(Response::ok)
+                .unwrap_or_else(|e| Response::error(500, e))
+        }
+        "/events" => Response::EventStream,
+        "/stats/daily" => {
+            let date: String = query_param(req, "date")?;
+            stats_response(app.state::<Database>().get_daily_stats(&date))
+        }
+        "/stats/weekly" => {
+            let week_start: String = query_param(req, "week_start")?;
+            stats_response(app.state::<Database>().get_weekly_stats(&week_start))
+        }
+        "/stats/monthly" => {
+            let year: i32 = query_param(req, "year")?;
+            let month: u32 = query_param(req, "month")?;
+            stats_response(app.state::<Database>().get_monthly_stats(year, month))
+        }
+        _ => Response::error(404, "Not found"),
+    };
+    Ok(response)
+}
+
+async fn write_json(stream: &mut TcpStream, code: u16, body: &str) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        code,
+        reason(code),
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await
+}
+
+/// Timer olaylarını Server-Sent Events olarak yayınla
+async fn stream_events(stream: &mut TcpStream, app: &AppHandle) -> std::io::Result<()> {
+    let mut events = app.state::<Arc<SharedTimerState>>().subscribe();
+
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .await?;
+
+    let mut keepalive = tokio::time::interval(Duration::from_secs(KEEPALIVE_SECS));
+    loop {
+        tokio::select! {
+            received = events.recv() => match received {
+                Ok(transition) => {
+                    let data = serde_json::to_string(&transition).unwrap_or_default();
+                    let frame = format!("event: {}\ndata: {}\n\n", transition.event.name(), data);
+                    stream.write_all(frame.as_bytes()).await?;
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return Ok(()),
+            },
+            _ = keepalive.tick() => stream.write_all(b": keepalive\n\n").await?,
+        }
+    }
+}
+
+async fn handle_connection(stream: TcpStream, app: AppHandle, token: Arc<String>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let head = read_request(&mut reader);
+    let Ok(Some(req)) = tokio::time::timeout(Duration::from_secs(HEAD_TIMEOUT_SECS), head).await
+    else {
+        return Ok(());
+    };
+    let mut stream = reader.into_inner();
+
+    if !authorized(&req, &token) {
+        return write_json(&mut stream, 401, r#"{"error":"Unauthorized"}"#).await;
+    }
+
+    match route(&app, &req).unwrap_or_else(|e| e) {
+        Response::Json(code, body) => write_json(&mut stream, code, &body).await,
+        Response::EventStream => stream_events(&mut stream, &app).await,
+    }
+}
+
+/// HTTP sunucusunu arka plan görevi olarak başlat
+pub fn start(app_handle: AppHandle, config: HttpConfig) {
+    if !config.enabled {
+        return;
+    }
+    if config.token.is_empty() {
+        eprintln!("HTTP API: config.json içinde token tanımlı değil, sunucu başlatılmadı");
+        return;
+    }
+
+    let token = Arc::new(config.token);
+
+    tauri::async_runtime::spawn(async move {
+        let listener = match TcpListener::bind(("127.0.0.1", config.port)).await {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("HTTP API: 127.0.0.1:{} dinlenemiyor: {}", config.port, e);
+                return;
+            }
+        };
+
+        loop {
+            let (stream, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    eprintln!("HTTP API: bağlantı kabul edilemedi: {}", e);
+                    continue;
+                }
+            };
+
+            let app = app_handle.clone();
+            let token = token.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = handle_connection(stream, app, token).await {
+                    eprintln!("HTTP API: bağlantı hatası: {}", e);
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_query_strings() {
+        assert_eq!(percent_decode("a%20b+c"), "a b c");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+
+        let query = parse_query("date=2024-01-02&token=a%2Bb&&flag");
+        assert_eq!(query.get("date").map(String::as_str), Some("2024-01-02"));
+        assert_eq!(query.get("token").map(String::as_str), Some("a+b"));
+        assert_eq!(query.get("flag").map(String::as_str), Some(""));
+    }
+
+    #[tokio::test]
+    async fn parses_request_head() {
+        let raw = b"GET /stats/daily?date=2024-01-02 HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n";
+        let req = read_request(&raw[..]).await.expect("valid request");
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.path, "/stats/daily");
+        assert_eq!(req.query.get("date").map(String::as_str), Some("2024-01-02"));
+        assert!(authorized(&req, "secret"));
+        assert!(!authorized(&req, "other"));
+    }
+
+    #[tokio::test]
+    async fn rejects_truncated_or_oversized_heads() {
+        assert!(read_request(&b"GET / HTTP/1.1\r\nHost: x"[..]).await.is_none());
+
+        // Tek satırlık dev istek de sınıra takılır
+        let long = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_HEAD_BYTES as usize));
+        assert!(read_request(long.as_bytes()).await.is_none());
+
+        let many = format!("GET / HTTP/1.1\r\n{}\r\n", "X-Pad: 0123456789\r\n".repeat(1000));
+        assert!(read_request(many.as_bytes()).await.is_none());
+    }
+
+    #[test]
+    fn token_comparison() {
+        assert!(token_matches("abc", "abc"));
+        assert!(!token_matches("abd", "abc"));
+        assert!(!token_matches("ab", "abc"));
+    }
+}
@@ -1,7 +1,8 @@
+use crate::control::TimerCommand;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 use zbus::Connection;
 
 /// Idle algılama modülü.
@@ -84,10 +85,10 @@ impl IdleDetector {
 
                 if is_idle && !was_idle {
                     // Kullanıcı boşta kaldı → timer'ı duraklat
-                    let _ = app_handle.emit("idle-pause", ());
+                    TimerCommand::Stop.dispatch(&app_handle);
                 } else if !is_idle && was_idle {
                     // Kullanıcı döndü → bildirim gönder
-                    let _ = app_handle.emit("idle-resume", ());
+                    TimerCommand::Start.dispatch(&app_handle);
                 }
 
                 was_idle = is_idle;
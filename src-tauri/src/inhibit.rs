@@ -0,0 +1,179 @@
+//! Ekran koruyucu ve uyku engelleme modülü.
+//! Aktif bir çalışma oturumu sürerken org.freedesktop.ScreenSaver.Inhibit ve
+//! logind Inhibit ile kilit alır; duraklatma, mola veya çıkışta kilidi bırakır.
+//! Uygulama çökerse kilitler kendiliğinden düşer: ScreenSaver çerezi D-Bus
+//! bağlantısına, logind kilidi ise açık tutulan dosya tanımlayıcısına bağlıdır.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{watch, Mutex};
+use zbus::zvariant::OwnedFd;
+use zbus::Connection;
+
+const APP_NAME: &str = "pomodoro";
+const REASON: &str = "Pomodoro çalışma oturumu sürüyor";
+
+/// Alınmış kilitler ve bunları bırakmak için gereken bilgiler
+#[derive(Default)]
+struct InhibitLocks {
+    conn: Option<Connection>,
+    system_conn: Option<Connection>,
+    screensaver_cookie: Option<u32>,
+    logind_fd: Option<OwnedFd>,
+}
+
+impl InhibitLocks {
+    async fn session(&mut self) -> Option<Connection> {
+        if self.conn.is_none() {
+            match Connection::session().await {
+                Ok(c) => self.conn = Some(c),
+                Err(e) => eprintln!("Inhibit: D-Bus oturum bağlantısı kurulamadı: {}", e),
+            }
+        }
+        self.conn.clone()
+    }
+
+    async fn system(&mut self) -> Option<Connection> {
+        if self.system_conn.is_none() {
+            match Connection::system().await {
+                Ok(c) => self.system_conn = Some(c),
+                Err(e) => eprintln!("Inhibit: D-Bus sistem bağlantısı kurulamadı: {}", e),
+            }
+        }
+        self.system_conn.clone()
+    }
+
+    async fn screensaver_proxy(&mut self) -> Option<zbus::Proxy<'static>> {
+        let conn = self.session().await?;
+        zbus::proxy::Builder::new(&conn)
+            .destination("org.freedesktop.ScreenSaver")
+            .ok()?
+            .path("/ScreenSaver")
+            .ok()?
+            .interface("org.freedesktop.ScreenSaver")
+            .ok()?
+            .build()
+            .await
+            .map_err(|e| eprintln!("Inhibit: ScreenSaver proxy oluşturulamadı: {}", e))
+            .ok()
+    }
+
+    async fn logind_proxy(&mut self) -> Option<zbus::Proxy<'static>> {
+        let conn = self.system().await?;
+        zbus::proxy::Builder::new(&conn)
+            .destination("org.freedesktop.login1")
+            .ok()?
+            .path("/org/freedesktop/login1")
+            .ok()?
+            .interface("org.freedesktop.login1.Manager")
+            .ok()?
+            .build()
+            .await
+            .map_err(|e| eprintln!("Inhibit: logind proxy oluşturulamadı: {}", e))
+            .ok()
+    }
+
+    /// Kilit durumunu istenen duruma getir (idempotent)
+    async fn apply(&mut self, inhibit: bool) {
+        if inhibit {
+            self.acquire().await;
+        } else {
+            self.release().await;
+        }
+    }
+
+    async fn acquire(&mut self) {
+        if self.screensaver_cookie.is_none() {
+            if let Some(proxy) = self.screensaver_proxy().await {
+                match proxy.call::<_, _, u32>("Inhibit", &(APP_NAME, REASON)).await {
+                    Ok(cookie) => self.screensaver_cookie = Some(cookie),
+                    Err(e) => eprintln!("Inhibit: ScreenSaver kilidi alınamadı: {}", e),
+                }
+            }
+        }
+
+        if self.logind_fd.is_none() {
+            if let Some(proxy) = self.logind_proxy().await {
+                match proxy
+                    .call::<_, _, OwnedFd>("Inhibit", &("sleep:idle", APP_NAME, REASON, "block"))
+                    .await
+                {
+                    Ok(fd) => self.logind_fd = Some(fd),
+                    Err(e) => eprintln!("Inhibit: logind kilidi alınamadı: {}", e),
+                }
+            }
+        }
+    }
+
+    async fn release(&mut self) {
+        if let Some(cookie) = self.screensaver_cookie.take() {
+            if let Some(proxy) = self.screensaver_proxy().await {
+                if let Err(e) = proxy.call::<_, _, ()>("UnInhibit", &(cookie,)).await {
+                    eprintln!("Inhibit: ScreenSaver kilidi bırakılamadı: {}", e);
+                }
+            }
+        }
+
+        // Dosya tanımlayıcısını kapatmak logind kilidini bırakır
+        self.logind_fd.take();
+    }
+}
+
+pub struct SleepInhibitor {
+    enabled: Arc<AtomicBool>,
+    wanted: watch::Sender<bool>,
+    locks: Arc<Mutex<InhibitLocks>>,
+}
+
+impl SleepInhibitor {
+    pub fn new() -> Self {
+        let (wanted, _) = watch::channel(false);
+        Self {
+            enabled: Arc::new(AtomicBool::new(false)),
+            wanted,
+            locks: Arc::new(Mutex::new(InhibitLocks::default())),
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        if !enabled {
+            self.wanted.send_replace(false);
+        }
+    }
+
+    /// Timer durumu değiştiğinde çağrılır; kilit yalnızca çalışma oturumu
+    /// aktifken ve ayar açıkken tutulur.
+    pub fn update(&self, session_active: bool) {
+        let inhibit = session_active && self.enabled.load(Ordering::SeqCst);
+        self.wanted.send_if_modified(|current| {
+            if *current == inhibit {
+                return false;
+            }
+            *current = inhibit;
+            true
+        });
+    }
+
+    /// Arka plan görevi olarak kilit yönetimini başlat
+    pub fn start(&self) {
+        let mut wanted = self.wanted.subscribe();
+        let locks = self.locks.clone();
+
+        tauri::async_runtime::spawn(async move {
+            while wanted.changed().await.is_ok() {
+                let inhibit = *wanted.borrow_and_update();
+                locks.lock().await.apply(inhibit).await;
+            }
+        });
+    }
+
+    /// Uygulama kapanırken tutulan kilitleri senkron olarak bırak
+    pub fn release_blocking(&self) {
+        self.wanted.send_replace(false);
+        let locks = self.locks.clone();
+        tauri::async_runtime::block_on(async move {
+            locks.lock().await.release().await;
+        });
+    }
+}
@@ -1,22 +1,57 @@
 mod audio;
 pub mod cli;
+mod config;
+mod control;
+mod cues;
 mod db;
 mod dbus;
+pub mod display;
+mod events;
+mod hooks;
+mod http;
 mod idle;
-
-use audio::AudioPlayer;
+mod inhibit;
+mod media;
+mod mpris;
+mod notify;
+mod portal;
+mod socket;
+mod shortcuts;
+mod sounds;
+mod speech;
+mod tray_icon;
+mod tray_menu;
+mod tray_status;
+mod window_state;
+
+use audio::{AudioPlayer, AudioStatus, Channel, NoiseColor};
+use config::AppConfig;
+use control::{Dispatcher, TimerCommand};
+use cues::CueScheduler;
 use db::{Database, DayStat};
-use dbus::SharedTimerState;
+use dbus::{SharedTimerState, TimerStatus};
+use events::TransitionReason;
+use hooks::HookRunner;
 use idle::IdleDetector;
-
+use inhibit::SleepInhibitor;
+use media::{MediaController, MediaPolicy};
+use notify::Notifier;
+use shortcuts::{ShortcutAction, ShortcutConflict, ShortcutManager};
+use sounds::{SoundEvent, SoundLibrary, SoundPack};
+use speech::{Announcer, Language};
+use tray_icon::TrayIconRenderer;
+use tray_menu::TrayMenu;
+use tray_status::TrayStatus;
+use window_state::WindowStateStore;
+
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{
-    image::Image,
-    menu::{Menu, MenuItem},
     tray::{TrayIcon, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager, State, WindowEvent,
+    webview::PageLoadEvent,
+    AppHandle, Manager, RunEvent, State, WindowEvent,
 };
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tauri_plugin_global_shortcut::ShortcutState;
 
 use std::path::PathBuf;
 
@@ -51,39 +86,100 @@ fn resolve_path(app: &AppHandle, name: &str, subdir: &str) -> Option<PathBuf> {
 
 // -- Ses Komutları (rodio) --
 
+/// Seçili ses paketinde olaya atanmış dosya, yoksa uygulamayla gelen `<name>.ogg`
+fn resolve_sound(
+    app: &AppHandle,
+    library: &SoundLibrary,
+    name: &str,
+    event: Option<SoundEvent>,
+) -> Result<PathBuf, String> {
+    if let Some(path) = event.and_then(|e| library.resolve(e)) {
+        return Ok(path);
+    }
+    let sound_name = format!("{}.ogg", name);
+    resolve_path(app, &sound_name, "../public/sounds")
+        .ok_or_else(|| format!("Sound not found: {}", sound_name))
+}
+
 #[tauri::command]
-fn play_sound(
+async fn play_sound(
     app: AppHandle,
     player: State<'_, AudioPlayer>,
+    library: State<'_, SoundLibrary>,
     name: String,
+    event: Option<SoundEvent>,
 ) -> Result<(), String> {
-    let sound_name = format!("{}.ogg", name);
-    let sound_path = resolve_path(&app, &sound_name, "../public/sounds")
-        .ok_or_else(|| format!("Sound not found: {}", sound_name))?;
+    let sound_path = resolve_sound(&app, &library, &name, event)?;
 
-    player.play(&sound_path, 0)
+    player.play(Channel::Alerts, &sound_path, false, 0).await
 }
 
 #[tauri::command]
-fn play_sound_loop(
+async fn play_sound_loop(
     app: AppHandle,
     player: State<'_, AudioPlayer>,
+    library: State<'_, SoundLibrary>,
     name: String,
+    channel: Option<Channel>,
+    event: Option<SoundEvent>,
 ) -> Result<(), String> {
-    let sound_name = format!("{}.ogg", name);
-    let sound_path = resolve_path(&app, &sound_name, "../public/sounds")
-        .ok_or_else(|| format!("Sound not found: {}", sound_name))?;
+    let sound_path = resolve_sound(&app, &library, &name, event)?;
 
-    // Ticking sesleri: 1.5s fade-in, döngüde çal
-    player.play_loop(&sound_path, 1500)
+    // Döngü sesleri: 1.5s fade-in, döngüde çal
+    player.play(channel.unwrap_or(Channel::Ticking), &sound_path, true, 1500).await
 }
 
 #[tauri::command]
-fn stop_sound(player: State<'_, AudioPlayer>) -> Result<(), String> {
-    player.stop();
+fn stop_sound(player: State<'_, AudioPlayer>, channel: Option<Channel>) -> Result<(), String> {
+    match channel {
+        Some(channel) => player.stop(channel),
+        None => player.stop_all(),
+    }
     Ok(())
 }
 
+#[tauri::command]
+async fn play_noise(player: State<'_, AudioPlayer>, color: NoiseColor) -> Result<(), String> {
+    player.play_noise(color).await
+}
+
+#[tauri::command]
+async fn play_metronome(player: State<'_, AudioPlayer>) -> Result<(), String> {
+    player.play_metronome().await
+}
+
+#[tauri::command]
+fn set_metronome_bpm(player: State<'_, AudioPlayer>, bpm: u32) -> Result<(), String> {
+    player.set_metronome_bpm(bpm);
+    Ok(())
+}
+
+#[tauri::command]
+fn set_volume(player: State<'_, AudioPlayer>, channel: Channel, volume: f32) -> Result<(), String> {
+    player.set_volume(channel, volume);
+    Ok(())
+}
+
+#[tauri::command]
+fn list_sound_packs(library: State<'_, SoundLibrary>) -> Result<Vec<SoundPack>, String> {
+    Ok(library.reload())
+}
+
+#[tauri::command]
+fn set_sound_pack(library: State<'_, SoundLibrary>, pack: Option<String>) -> Result<(), String> {
+    library.set_active(pack)
+}
+
+#[tauri::command]
+async fn get_audio_status(player: State<'_, AudioPlayer>) -> Result<AudioStatus, String> {
+    Ok(player.status().await)
+}
+
+#[tauri::command]
+async fn get_volumes(player: State<'_, AudioPlayer>) -> Result<HashMap<Channel, f32>, String> {
+    Ok(player.volumes().await)
+}
+
 // -- Pencere Komutları --
 
 #[tauri::command]
@@ -103,31 +199,25 @@ fn set_fullscreen(window: tauri::Window, fullscreen: bool) -> Result<(), String>
 // -- Tray Komutları --
 
 #[tauri::command]
-fn update_tray_icon(app: AppHandle, mode: String) -> Result<(), String> {
-    let tray = app.tray_by_id("main_tray").ok_or("Tray not found")?;
-
-    let icon_file = match mode.as_str() {
-        "work" => "work.png",
-        "shortBreak" => "short-break.png",
-        "longBreak" => "long-break.png",
-        _ => "work.png",
-    };
-
-    if let Some(icon_path) = resolve_path(&app, icon_file, "icons") {
-        match Image::from_path(&icon_path) {
-            Ok(icon) => {
-                let _ = tray.set_icon(Some(icon));
-            }
-            Err(_) => {
-                if let Some(icon) = app.default_window_icon() {
-                    let _ = tray.set_icon(Some(icon.clone()));
-                }
-            }
-        }
-    } else if let Some(icon) = app.default_window_icon() {
-        let _ = tray.set_icon(Some(icon.clone()));
+fn update_tray_icon(
+    app: AppHandle,
+    renderer: State<'_, TrayIconRenderer>,
+    mode: String,
+) -> Result<(), String> {
+    // Dinamik ikon açıkken ikonu TrayIconRenderer çizer
+    if renderer.is_dynamic() {
+        return Ok(());
     }
+    tray_icon::set_static_icon(&app, &mode)
+}
 
+#[tauri::command]
+fn set_tray_icon_style(
+    renderer: State<'_, TrayIconRenderer>,
+    dynamic: bool,
+    show_minutes: bool,
+) -> Result<(), String> {
+    renderer.set_style(dynamic, show_minutes);
     Ok(())
 }
 
@@ -136,10 +226,14 @@ fn update_tray_icon(app: AppHandle, mode: String) -> Result<(), String> {
 #[tauri::command]
 fn save_session(
     db: State<'_, Database>,
+    tray_status: State<'_, TrayStatus>,
     state: String,
     elapsed: f64,
 ) -> Result<(), String> {
-    db.save_session(&state, elapsed).map_err(|e| e.to_string())
+    db.save_session(&state, elapsed).map_err(|e| e.to_string())?;
+    // Tray ipucundaki bugünkü sayı güncellensin
+    tray_status.refresh();
+    Ok(())
 }
 
 #[tauri::command]
@@ -173,40 +267,122 @@ fn set_idle_detection(idle: State<'_, IdleDetector>, enabled: bool) -> Result<()
     Ok(())
 }
 
+// -- Uyku/Ekran Koruyucu Engelleme --
+
+#[tauri::command]
+fn set_sleep_inhibit(inhibitor: State<'_, SleepInhibitor>, enabled: bool) -> Result<(), String> {
+    inhibitor.set_enabled(enabled);
+    Ok(())
+}
+
+// -- Pencere --
+
+#[tauri::command]
+fn set_start_minimized(
+    window_state: State<'_, WindowStateStore>,
+    enabled: bool,
+) -> Result<(), String> {
+    window_state.set_start_minimized(enabled);
+    Ok(())
+}
+
+// -- Medya Oynatıcı Kontrolü --
+
+#[tauri::command]
+fn set_media_policy(media: State<'_, MediaController>, policy: MediaPolicy) -> Result<(), String> {
+    media.set_policy(policy);
+    Ok(())
+}
+
+// -- Bildirimler --
+
+#[tauri::command]
+fn set_notification_warning(notifier: State<'_, Notifier>, seconds: u32) -> Result<(), String> {
+    notifier.set_warning_seconds(seconds);
+    Ok(())
+}
+
+#[tauri::command]
+fn set_sound_cues(
+    cues: State<'_, CueScheduler>,
+    warning_seconds: u32,
+    countdown_seconds: u32,
+) -> Result<(), String> {
+    cues.set_warning_seconds(warning_seconds);
+    cues.set_countdown_seconds(countdown_seconds);
+    Ok(())
+}
+
+#[tauri::command]
+fn set_announcements(
+    announcer: State<'_, Announcer>,
+    enabled: bool,
+    language: Language,
+) -> Result<(), String> {
+    announcer.set_enabled(enabled);
+    announcer.set_language(language);
+    Ok(())
+}
+
 // -- D-Bus Durum Güncelleme --
 
 #[tauri::command]
 fn update_timer_status(
     shared_state: State<'_, Arc<SharedTimerState>>,
+    inhibitor: State<'_, SleepInhibitor>,
     mode: String,
     time_left: u32,
     is_active: bool,
     sessions_completed: u32,
+    duration: u32,
+    reason: Option<TransitionReason>,
 ) -> Result<(), String> {
-    shared_state.update(mode, time_left, is_active, sessions_completed);
+    inhibitor.update(is_active && mode == "work");
+    shared_state.update(
+        TimerStatus {
+            state: mode,
+            time_left,
+            is_active,
+            sessions_completed,
+            duration,
+        },
+        reason,
+    );
+    Ok(())
+}
+
+/// Kısayol tablosunu yeniden kaydet; kaydedilemeyenler döndürülür
+#[tauri::command]
+fn set_shortcuts(
+    app: AppHandle,
+    manager: State<'_, ShortcutManager>,
+    shortcuts: HashMap<ShortcutAction, String>,
+) -> Result<Vec<ShortcutConflict>, String> {
+    Ok(manager.apply(&app, &shortcuts))
+}
+
+#[tauri::command]
+fn get_shortcut_conflicts(
+    manager: State<'_, ShortcutManager>,
+) -> Result<Vec<ShortcutConflict>, String> {
+    Ok(manager.conflicts())
+}
+
+/// Frontend timer-command dinleyicilerini kurdu; bekleyen komutlar iletilir
+#[tauri::command]
+fn frontend_ready(app: AppHandle, dispatcher: State<'_, Dispatcher>) -> Result<(), String> {
+    dispatcher.set_ready(&app);
     Ok(())
 }
 
 // -- Tray Yapılandırması --
 
-fn build_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    let toggle = MenuItem::with_id(app, "toggle", "Göster/Gizle", true, None::<&str>)?;
-    let start_stop =
-        MenuItem::with_id(app, "start_stop", "Başlat/Durdur", true, None::<&str>)?;
-    let pause_resume =
-        MenuItem::with_id(app, "pause_resume", "Duraklat/Devam", true, None::<&str>)?;
-    let skip = MenuItem::with_id(app, "skip", "Atla", true, None::<&str>)?;
-    let reset = MenuItem::with_id(app, "reset", "Sıfırla", true, None::<&str>)?;
-    let quit = MenuItem::with_id(app, "quit", "Çıkış", true, None::<&str>)?;
-
-    let tray_menu = Menu::with_items(
-        app,
-        &[&toggle, &start_stop, &pause_resume, &skip, &reset, &quit],
-    )?;
+fn build_tray(app: &tauri::App) -> Result<TrayMenu, Box<dyn std::error::Error>> {
+    let tray_menu = TrayMenu::new(app.handle())?;
 
     let _tray = TrayIconBuilder::with_id("main_tray")
         .icon(app.default_window_icon().unwrap().clone())
-        .menu(&tray_menu)
+        .menu(tray_menu.menu())
         .tooltip("pomodoro")
         .show_menu_on_left_click(false)
         .on_tray_icon_event(|tray: &TrayIcon, event: TrayIconEvent| {
@@ -239,18 +415,11 @@ fn build_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
             }
-            "start_stop" => {
-                let _ = app.emit("tray-start-stop", ());
-            }
-            "pause_resume" => {
-                let _ = app.emit("tray-pause-resume", ());
-            }
-            "skip" => {
-                let _ = app.emit("tray-skip", ());
-            }
-            "reset" => {
-                let _ = app.emit("reset-timer", ());
-            }
+            "start_pause" => TimerCommand::Toggle.dispatch(app),
+            "extend_1" => TimerCommand::Extend(60).dispatch(app),
+            "extend_5" => TimerCommand::Extend(5 * 60).dispatch(app),
+            "skip" => TimerCommand::Skip.dispatch(app),
+            "reset" => TimerCommand::Reset.dispatch(app),
             "quit" => {
                 app.exit(0);
             }
@@ -258,89 +427,242 @@ fn build_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
         })
         .build(app)?;
 
-    Ok(())
+    Ok(tray_menu)
 }
 
 // -- Uygulama Giriş Noktası --
 
+/// Başlangıçta gizli oluşturulan ana pencereyi göster
+fn show_startup_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.show();
+    }
+}
+
+/// `start_hidden`: pencereyi gösterme, yalnızca tray'de başla (--minimized, D-Bus etkinleştirmesi)
+/// `restore`: son çıkışta pencere gizliyse gizli başla (--restore)
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
+pub fn run(start_hidden: bool, restore: bool) {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_notification::init())
-        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
-        .setup(|app| {
-            // Audio player başlat (rodio)
-            let player =
-                AudioPlayer::new().expect("Failed to initialize audio player");
-            app.manage(player);
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app_handle, shortcut, event| {
+                    if event.state == ShortcutState::Pressed {
+                        if let Some(manager) = app_handle.try_state::<ShortcutManager>() {
+                            manager.handle(app_handle, shortcut);
+                        }
+                    }
+                })
+                .build(),
+        )
+        .on_page_load(|webview, payload| {
+            // Sayfa (yeniden) yüklenirken dinleyiciler kurulana kadar komutları beklet
+            if let PageLoadEvent::Started = payload.event() {
+                if let Some(dispatcher) = webview.try_state::<Dispatcher>() {
+                    dispatcher.set_not_ready();
+                }
+            }
+        })
+        .setup(move |app| {
+            // Tray, kısayol, D-Bus vb. timer komutlarının frontend'e tek iletim yolu
+            app.manage(Dispatcher::new());
+
+            // Audio player (rodio): ayrı bir thread'de çalışır, çıkış cihazı ilk seste açılır
+            app.manage(AudioPlayer::new(app.handle().clone()));
+
+            // Kullanıcı ses paketleri (~/.local/share/pomodoro/sounds)
+            app.manage(SoundLibrary::new());
 
             // Veritabanı başlat
             let app_data_dir = app.path().app_data_dir()?;
             let database =
-                Database::new(app_data_dir).expect("Failed to initialize database");
+                Database::new(app_data_dir.clone()).expect("Failed to initialize database");
             app.manage(database);
 
+            // Pencere konumu, boyutu ve görünürlüğü (window-state.json)
+            app.manage(WindowStateStore::new(app_data_dir));
+
+            // Pencere yapılandırmada gizli oluşturulur; son geometri hemen uygulanır.
+            // Pencere D-Bus adı alındıktan sonra gösterilir, böylece aynı anda
+            // başlatılıp kapanan ikinci bir örneğin penceresi bir an görünmez.
+            // --minimized/--activated, ayar veya (--restore ile) son çıkışta gizli
+            // olması durumunda tray'de kalır.
+            let window_state = app.state::<WindowStateStore>();
+            let show_window = !start_hidden && !window_state.start_hidden(restore);
+            if let Some(window) = app.get_webview_window("main") {
+                window_state.restore(&window);
+            }
+
             // D-Bus paylaşılan durum ve servisi
             let shared_state = Arc::new(SharedTimerState::new());
             app.manage(shared_state.clone());
 
+            // Bitiş öncesi uyarı çanı ve geri sayım tıkları
+            let cues = CueScheduler::new();
+            cues.start(app.handle().clone(), shared_state.clone());
+            let cue_events = cues.subscribe();
+            app.manage(cues);
+
             let dbus_handle = app.handle().clone();
             let dbus_state = shared_state.clone();
             tauri::async_runtime::spawn(async move {
-                let _conn = match dbus::start_dbus_service(dbus_handle, dbus_state).await {
+                let conn = match dbus::start_dbus_service(dbus_handle.clone(), dbus_state.clone())
+                    .await
+                {
                     Ok(conn) => conn,
+                    // Aynı anda başlatılan başka bir örnek adı önce aldı: ona devret
+                    Err(zbus::Error::NameTaken) => {
+                        eprintln!("Pomodoro zaten çalışıyor, bu örnek kapatılıyor");
+                        cli::activate_running(!start_hidden && !restore).await;
+                        dbus_handle.exit(0);
+                        return;
+                    }
                     Err(e) => {
                         eprintln!("D-Bus service failed to start: {}", e);
+                        if show_window {
+                            show_startup_window(&dbus_handle);
+                        }
                         return;
                     }
                 };
+                if show_window {
+                    show_startup_window(&dbus_handle);
+                }
+                dbus::forward_cues(&conn, cue_events);
+                // Medya tuşları ve panel widget'ları için MPRIS arayüzü
+                if let Err(e) = mpris::serve(&conn, dbus_handle, dbus_state).await {
+                    eprintln!("MPRIS service failed to start: {}", e);
+                }
                 // D-Bus bağlantısı uygulama ömrü boyunca canlı kalmalı
                 std::future::pending::<()>().await;
             });
 
+            // D-Bus olmayan ortamlar için Unix soketi
+            socket::start(app.handle().clone(), shared_state.clone());
+
+            // Bildirimler (oturum sonu ve bitiş öncesi uyarı)
+            let notifier = Notifier::new();
+            notifier.start(app.handle().clone(), shared_state.clone());
+            app.manage(notifier);
+
+            // Oturum bitişlerinde sesli duyuru (spd-say / espeak-ng)
+            let announcer = Announcer::new();
+            announcer.start(shared_state.clone());
+            app.manage(announcer);
+
+            // Kullanıcı yapılandırması
+            let config = AppConfig::load();
+
+            // Mod geçişlerinde medya oynatıcılarını duraklat/başlat
+            let media = MediaController::new();
+            media.start(shared_state.clone(), config.media.player);
+            app.manage(media);
+
+            // Olay hook'ları
+            HookRunner::new(config.hooks).start(shared_state.clone());
+
+            // Yerel HTTP/JSON API (config.json'da etkinse)
+            http::start(app.handle().clone(), config.http);
+
             // Idle detection başlat
             let idle_detector = IdleDetector::new();
             idle_detector.start(app.handle().clone());
             app.manage(idle_detector);
 
-            // Tray oluştur
-            build_tray(app)?;
-
-
+            // Uyku/ekran koruyucu engelleyici başlat
+            let sleep_inhibitor = SleepInhibitor::new();
+            sleep_inhibitor.start();
+            app.manage(sleep_inhibitor);
 
-            // Global kısayol: Ctrl+Alt+P → timer toggle
-            app.global_shortcut().on_shortcut(
-                "ctrl+alt+p",
-                |app_handle: &AppHandle, _shortcut, event| {
-                    if event.state == ShortcutState::Pressed {
-                        let _ = app_handle.emit("tray-start-stop", ());
-                    }
-                },
-            )?;
+            // Tray oluştur
+            let tray_menu = build_tray(app)?;
+
+            // Kalan süreyi gösteren dinamik tray ikonu
+            let tray_renderer = TrayIconRenderer::new();
+            tray_renderer.start(app.handle().clone(), shared_state.clone());
+            app.manage(tray_renderer);
+
+            // Mod, kalan süre ve bugünkü sayıyı gösteren tray ipucu, başlığı ve menüsü
+            let tray_status = TrayStatus::new();
+            tray_status.start(app.handle().clone(), shared_state.clone(), tray_menu);
+            app.manage(tray_status);
+
+            // Global kısayollar: ayarlar yüklenene kadar varsayılan tablo (Ctrl+Alt+P → toggle);
+            // kaydedilemeyenler kurulumu durdurmaz, çakışma olarak raporlanır
+            let shortcut_manager = ShortcutManager::new();
+            shortcut_manager.apply(app.handle(), &shortcuts::default_bindings());
+            app.manage(shortcut_manager);
+            // Wayland'de XDG GlobalShortcuts portalına geçilir
+            app.state::<ShortcutManager>().start(app.handle().clone());
 
             Ok(())
         })
         .on_window_event(|window, event| {
+            let window_state = window.try_state::<WindowStateStore>();
+            if let Some(window_state) = &window_state {
+                window_state.track(window, event);
+            }
             if let WindowEvent::CloseRequested { api, .. } = event {
                 api.prevent_close();
                 let _ = window.hide();
+                if let Some(window_state) = &window_state {
+                    window_state.save(false);
+                }
             }
         })
         .invoke_handler(tauri::generate_handler![
             set_always_on_top,
             update_tray_icon,
+            set_tray_icon_style,
             set_fullscreen,
             play_sound,
             play_sound_loop,
             stop_sound,
+            play_noise,
+            play_metronome,
+            set_metronome_bpm,
+            set_volume,
+            get_volumes,
+            get_audio_status,
+            list_sound_packs,
+            set_sound_pack,
             save_session,
             get_daily_stats,
             get_weekly_stats,
             get_monthly_stats,
             update_timer_status,
-            set_idle_detection
+            frontend_ready,
+            set_shortcuts,
+            get_shortcut_conflicts,
+            set_idle_detection,
+            set_sleep_inhibit,
+            set_notification_warning,
+            set_sound_cues,
+            set_announcements,
+            set_media_policy,
+            set_start_minimized
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| match event {
+            RunEvent::ExitRequested { .. } => {
+                // Pencereler henüz kapanmadan son görünürlüğü kaydet
+                if let (Some(window_state), Some(window)) = (
+                    app.try_state::<WindowStateStore>(),
+                    app.get_webview_window("main"),
+                ) {
+                    window_state.save(window.is_visible().unwrap_or(true));
+                }
+            }
+            RunEvent::Exit => {
+                // Çıkışta tutulan uyku/ekran koruyucu kilitlerini bırak
+                if let Some(inhibitor) = app.try_state::<SleepInhibitor>() {
+                    inhibitor.release_blocking();
+                }
+                socket::cleanup();
+            }
+            _ => {}
+        });
 }
@@ -2,32 +2,52 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use clap::Parser;
-use pomodoro_lib::cli::{Cli, handle_cli};
+use pomodoro_lib::cli::{activate_running, handle_cli, handle_cli_spawn, Cli};
 
 fn main() {
-    // Force Server-Side Decorations (SSD) on Wayland/KDE.
-    // Must be set BEFORE Tauri/GTK initializes.
-    std::env::set_var("GTK_CSD", "0");
-    std::env::set_var("GDK_BACKEND", "x11");
-
     let cli = Cli::parse();
 
     if let Some(command) = &cli.command {
         // CLI komutu varsa D-Bus üzerinden çalışan uygulamaya gönder
         let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
-        match rt.block_on(handle_cli(command)) {
+        let result = if cli.spawn {
+            rt.block_on(handle_cli_spawn(command, cli.backend))
+        } else {
+            rt.block_on(handle_cli(command))
+        };
+        match result {
             Ok(true) => {}
+            Ok(false) if cli.spawn => {
+                eprintln!("Pomodoro başlatıldı ancak zamanında yanıt vermedi.");
+                std::process::exit(1);
+            }
             Ok(false) => {
-                eprintln!("Pomodoro uygulaması çalışmıyor. Önce uygulamayı başlatın.");
+                eprintln!("Pomodoro uygulaması çalışmıyor. Önce uygulamayı başlatın veya --spawn kullanın.");
                 std::process::exit(1);
             }
             Err(e) => {
-                eprintln!("D-Bus hatası: {}", e);
+                eprintln!("Komut gönderilemedi: {}", e);
                 std::process::exit(1);
             }
         }
     } else {
-        // Argüman yoksa uygulamayı normal başlat
-        pomodoro_lib::run();
+        // GDK_BACKEND / GTK_CSD must be set BEFORE Tauri/GTK initializes.
+        // Ortam değişkenleri, tokio runtime'ı başka thread'ler açmadan önce ayarlanır.
+        pomodoro_lib::display::configure(cli.backend);
+
+        // Uygulama zaten çalışıyorsa penceresini öne getir ve çık (tek örnek).
+        // D-Bus/systemd etkinleştirmesinde, --minimized ve --restore ile pencere gösterilmez.
+        let show = !cli.minimized && !cli.restore;
+        let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+        if !cli.activated && rt.block_on(activate_running(show)) {
+            if show {
+                println!("Pomodoro zaten çalışıyor, pencere gösterildi.");
+            }
+            return;
+        }
+        drop(rt);
+
+        // Argüman yoksa uygulamayı normal başlat.
+        pomodoro_lib::run(cli.minimized || cli.activated, cli.restore);
     }
 }
@@ -0,0 +1,290 @@
+//! Medya oynatıcı kontrolü (MPRIS istemcisi).
+//! Mod geçişlerinde oturum D-Bus'ındaki org.mpris.MediaPlayer2.* oynatıcılarını
+//! duraklatır veya başlatır. Yalnızca bu modülün duraklattığı/başlattığı
+//! oynatıcılar hatırlanır ve geri alınır; kullanıcının kendi durdurduğu
+//! oynatıcılara dokunulmaz. "Çalışmada Çal" politikası config.json'daki
+//! `media.player` oynatıcısını, yoksa bu modülün en son duraklattığı oynatıcıyı
+//! başlatır.
+
+use crate::dbus::SharedTimerState;
+use crate::events::{TimerEvent, TimerTransition};
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+use zbus::proxy::CacheProperties;
+use zbus::Connection;
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+/// Kendi MPRIS arayüzümüz (bkz. mpris.rs) kontrol edilmez
+const OWN_PLAYER: &str = "org.mpris.MediaPlayer2.pomodoro";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MediaPolicy {
+    /// Oynatıcılara dokunma
+    None,
+    /// Mola başlarken çalan oynatıcıları duraklat, çalışmaya dönünce devam ettir
+    PauseOnBreak,
+    /// Çalışma başlarken oynatıcıyı başlat, çalışma bitince/duraklayınca duraklat
+    PlayOnWork,
+}
+
+async fn player_proxy<'a>(conn: &Connection, name: &'a str) -> zbus::Result<zbus::Proxy<'a>> {
+    zbus::proxy::Builder::new(conn)
+        .destination(name)?
+        .path("/org/mpris/MediaPlayer2")?
+        .interface("org.mpris.MediaPlayer2.Player")?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await
+}
+
+/// Oturum bus'ındaki MPRIS oynatıcılarının adları (sıralı)
+async fn list_players(conn: &Connection) -> Vec<String> {
+    let names = match zbus::fdo::DBusProxy::new(conn).await {
+        Ok(proxy) => proxy.list_names().await.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Medya: D-Bus adları listelenemedi: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut players: Vec<String> = names
+        .iter()
+        .map(|n| n.to_string())
+        .filter(|n| n.starts_with(MPRIS_PREFIX) && n != OWN_PLAYER)
+        .collect();
+    players.sort();
+    players
+}
+
+async fn playback_status(conn: &Connection, name: &str) -> Option<String> {
+    let proxy = player_proxy(conn, name).await.ok()?;
+    proxy.get_property::<String>("PlaybackStatus").await.ok()
+}
+
+async fn call(conn: &Connection, name: &str, method: &str) -> bool {
+    let result = match player_proxy(conn, name).await {
+        Ok(proxy) => proxy.call_method(method, &()).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = &result {
+        eprintln!("Medya: {} {} çağrısı başarısız: {}", name, method, e);
+    }
+    result.is_ok()
+}
+
+/// Adın yapılandırılan oynatıcıya ait olup olmadığı ("spotify" →
+/// org.mpris.MediaPlayer2.spotify veya org.mpris.MediaPlayer2.spotify.instance42)
+fn matches_player(name: &str, configured: &str) -> bool {
+    name.strip_prefix(MPRIS_PREFIX)
+        .is_some_and(|rest| rest == configured || rest.starts_with(&format!("{}.", configured)))
+}
+
+/// Başlatılacak oynatıcı: yapılandırılan, yoksa en son duraklatılan, yoksa ilki
+fn choose_player<'a>(
+    players: &'a [String],
+    preferred: Option<&str>,
+    last_paused: Option<&str>,
+) -> Option<&'a String> {
+    preferred
+        .and_then(|p| players.iter().find(|n| matches_player(n, p)))
+        .or_else(|| last_paused.and_then(|l| players.iter().find(|n| n.as_str() == l)))
+        .or_else(|| players.first())
+}
+
+/// Çalan tüm oynatıcıları duraklat, duraklatılanların adlarını döndür
+async fn pause_playing(conn: &Connection) -> Vec<String> {
+    let mut paused = Vec::new();
+    for name in list_players(conn).await {
+        if playback_status(conn, &name).await.as_deref() == Some("Playing")
+            && call(conn, &name, "Pause").await
+        {
+            paused.push(name);
+        }
+    }
+    paused
+}
+
+/// Verilen oynatıcıları (hâlâ bus'taysa) tekrar başlat
+async fn resume(conn: &Connection, names: &[String]) {
+    for name in names {
+        if playback_status(conn, name).await.is_some_and(|s| s != "Playing") {
+            call(conn, name, "Play").await;
+        }
+    }
+}
+
+pub struct MediaController {
+    policy: Arc<Mutex<MediaPolicy>>,
+}
+
+impl MediaController {
+    pub fn new() -> Self {
+        Self {
+            policy: Arc::new(Mutex::new(MediaPolicy::None)),
+        }
+    }
+
+    pub fn set_policy(&self, policy: MediaPolicy) {
+        match self.policy.lock() {
+            Ok(mut p) => *p = policy,
+            Err(e) => eprintln!("MediaController::set_policy mutex poisoned: {}", e),
+        }
+    }
+
+    /// Arka plan görevi olarak mod geçişlerini dinle.
+    /// `preferred`: "Çalışmada Çal" ile başlatılacak oynatıcı (config.json)
+    pub fn start(&self, shared_state: Arc<SharedTimerState>, preferred: Option<String>) {
+        let policy = self.policy.clone();
+        let mut events = shared_state.subscribe();
+
+        tauri::async_runtime::spawn(async move {
+            let conn = match Connection::session().await {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("Medya: D-Bus bağlantısı kurulamadı: {}", e);
+                    return;
+                }
+            };
+
+            // Bu modülün duraklattığı veya başlattığı oynatıcılar
+            let mut touched: Vec<String> = Vec::new();
+            // Bu modülün en son duraklattığı oynatıcı
+            let mut last_paused: Option<String> = None;
+
+            loop {
+                let transition = match events.recv().await {
+                    Ok(t) => t,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+
+                let current = policy.lock().map(|p| *p).unwrap_or(MediaPolicy::None);
+                match current {
+                    MediaPolicy::None => touched.clear(),
+                    MediaPolicy::PauseOnBreak => {
+                        pause_on_break(&conn, &transition, &mut touched, &mut last_paused).await
+                    }
+                    MediaPolicy::PlayOnWork => {
+                        play_on_work(
+                            &conn,
+                            &transition,
+                            &mut touched,
+                            preferred.as_deref(),
+                            &mut last_paused,
+                        )
+                        .await
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Mod değişiminin hedefi (bitti veya atlandı)
+fn switched_to(event: &TimerEvent) -> Option<&str> {
+    match event {
+        TimerEvent::Finished { to, .. } | TimerEvent::Skipped { to, .. } => Some(to),
+        _ => None,
+    }
+}
+
+async fn pause_on_break(
+    conn: &Connection,
+    transition: &TimerTransition,
+    paused: &mut Vec<String>,
+    last_paused: &mut Option<String>,
+) {
+    match switched_to(&transition.event) {
+        Some("work") => resume(conn, &std::mem::take(paused)).await,
+        Some(_) => {
+            for name in pause_playing(conn).await {
+                *last_paused = Some(name.clone());
+                if !paused.contains(&name) {
+                    paused.push(name);
+                }
+            }
+        }
+        None => {}
+    }
+}
+
+async fn play_on_work(
+    conn: &Connection,
+    transition: &TimerTransition,
+    started: &mut Vec<String>,
+    preferred: Option<&str>,
+    last_paused: &mut Option<String>,
+) {
+    let status = &transition.status;
+    let focusing = status.state == "work" && status.is_active;
+
+    match (&transition.event, focusing) {
+        (TimerEvent::Started | TimerEvent::Resumed, true) => {
+            // Seçilen oynatıcı kapanana kadar hep aynısı kullanılır
+            let players = list_players(conn).await;
+            started.retain(|name| players.contains(name));
+            if started.is_empty() {
+                if let Some(name) = choose_player(&players, preferred, last_paused.as_deref()) {
+                    started.push(name.clone());
+                }
+            }
+            resume(conn, started).await;
+        }
+        (
+            TimerEvent::Paused
+            | TimerEvent::Reset
+            | TimerEvent::Finished { .. }
+            | TimerEvent::Skipped { .. },
+            false,
+        ) => {
+            for name in started.iter() {
+                if playback_status(conn, name).await.as_deref() == Some("Playing")
+                    && call(conn, name, "Pause").await
+                {
+                    *last_paused = Some(name.clone());
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players() -> Vec<String> {
+        ["org.mpris.MediaPlayer2.firefox.instance_1_42", "org.mpris.MediaPlayer2.spotify"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    #[test]
+    fn configured_player_wins() {
+        let players = players();
+        let chosen = choose_player(&players, Some("spotify"), Some(&players[0]));
+        assert_eq!(chosen, Some(&players[1]));
+        // Örnek sonekli adlar da eşleşir
+        let chosen = choose_player(&players, Some("firefox"), None);
+        assert_eq!(chosen, Some(&players[0]));
+    }
+
+    #[test]
+    fn falls_back_to_last_paused_then_first() {
+        let players = players();
+        assert_eq!(
+            choose_player(&players, Some("vlc"), Some(&players[1])),
+            Some(&players[1])
+        );
+        assert_eq!(choose_player(&players, None, Some("gone")), Some(&players[0]));
+        assert_eq!(choose_player(&[], None, None), None);
+    }
+
+    #[test]
+    fn player_names_match_whole_segments() {
+        assert!(matches_player("org.mpris.MediaPlayer2.spotify", "spotify"));
+        assert!(!matches_player("org.mpris.MediaPlayer2.spotifyd", "spotify"));
+    }
+}
@@ -0,0 +1,351 @@
+//! MPRIS medya oynatıcı arayüzü.
+//! Timer'ı org.mpris.MediaPlayer2.pomodoro olarak yayınlar; böylece medya tuşları,
+//! `playerctl` ve hazır panel widget'ları timer'ı özel betik gerektirmeden yönetebilir.
+//! Play/Pause/Next → toggle/skip, Position/Length → geçen/planlanan oturum süresi.
+//! Oturuma eklenen süre Length'e dahildir; uzatmadan sonra Position sıfırlanmaz.
+
+use crate::control::{self, TimerCommand};
+use crate::dbus::{SharedTimerState, TimerStatus};
+use crate::events::TimerEvent;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::broadcast::error::RecvError;
+use zbus::zvariant::{ObjectPath, OwnedValue, Str, Value};
+use zbus::{interface, Connection, SignalContext};
+
+const MPRIS_NAME: &str = "org.mpris.MediaPlayer2.pomodoro";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// Mod adının kullanıcıya gösterilen karşılığı
+pub(crate) fn mode_title(state: &str) -> &str {
+    match state {
+        "work" => "Odaklan",
+        "shortBreak" => "Kısa Mola",
+        "longBreak" => "Uzun Mola",
+        _ => state,
+    }
+}
+
+/// Uzatmalar dahil oturum uzunluğu (saniye); `extended` bu oturumda eklenen süredir
+fn session_length(status: &TimerStatus, extended: u32) -> u32 {
+    // Kaçırılan bir uzatma olayında bile kalan süre uzunluğu aşmasın
+    status.duration.saturating_add(extended).max(status.time_left)
+}
+
+/// Mikro saniye cinsinden geçen süre
+fn position_us(status: &TimerStatus, extended: u32) -> i64 {
+    session_length(status, extended).saturating_sub(status.time_left) as i64 * 1_000_000
+}
+
+fn metadata(status: &TimerStatus, extended: u32) -> HashMap<String, OwnedValue> {
+    let mut map = HashMap::new();
+
+    // Her oturum ayrı bir "parça" olarak görünür
+    let track_id = format!(
+        "/com/osmandulundu/pomodoro/session/{}_{}",
+        status.sessions_completed, status.state
+    );
+    if let Ok(path) = ObjectPath::try_from(track_id) {
+        map.insert("mpris:trackid".to_string(), OwnedValue::from(path));
+    }
+
+    let length = session_length(status, extended) as i64 * 1_000_000;
+    map.insert("mpris:length".to_string(), OwnedValue::from(length));
+    map.insert(
+        "xesam:title".to_string(),
+        OwnedValue::from(Str::from(mode_title(&status.state).to_string())),
+    );
+    map.insert(
+        "xesam:album".to_string(),
+        OwnedValue::from(Str::from(format!("Oturum {}", status.sessions_completed))),
+    );
+    if let Ok(artist) = OwnedValue::try_from(Value::from(vec!["Pomodoro"])) {
+        map.insert("xesam:artist".to_string(), artist);
+    }
+    map
+}
+
+/// org.mpris.MediaPlayer2 kök arayüzü
+struct MprisRoot {
+    app_handle: AppHandle,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl MprisRoot {
+    /// Ana pencereyi göster
+    async fn raise(&self) {
+        control::show_window(&self.app_handle);
+    }
+
+    async fn quit(&self) {
+        self.app_handle.exit(0);
+    }
+
+    #[zbus(property)]
+    async fn can_quit(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    async fn can_raise(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    async fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    async fn identity(&self) -> String {
+        "Pomodoro".to_string()
+    }
+
+    #[zbus(property)]
+    async fn desktop_entry(&self) -> String {
+        "pomodoro-tauri".to_string()
+    }
+
+    #[zbus(property)]
+    async fn supported_uri_schemes(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(property)]
+    async fn supported_mime_types(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// org.mpris.MediaPlayer2.Player arayüzü
+struct MprisPlayer {
+    shared_state: Arc<SharedTimerState>,
+    app_handle: AppHandle,
+    /// Mevcut oturuma eklenen toplam süre (saniye)
+    extended: Arc<AtomicU32>,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl MprisPlayer {
+    /// Sonraki oturuma geç
+    async fn next(&self) {
+        TimerCommand::Skip.dispatch(&self.app_handle);
+    }
+
+    /// Mevcut oturumu baştan başlat
+    async fn previous(&self) {
+        TimerCommand::Reset.dispatch(&self.app_handle);
+    }
+
+    async fn pause(&self) {
+        TimerCommand::Stop.dispatch(&self.app_handle);
+    }
+
+    async fn play_pause(&self) {
+        TimerCommand::Toggle.dispatch(&self.app_handle);
+    }
+
+    /// Durdur: timer'ı sıfırla
+    async fn stop(&self) {
+        TimerCommand::Reset.dispatch(&self.app_handle);
+    }
+
+    async fn play(&self) {
+        TimerCommand::Start.dispatch(&self.app_handle);
+    }
+
+    /// Sarma desteklenmiyor (CanSeek = false)
+    async fn seek(&self, _offset: i64) {}
+
+    async fn set_position(&self, _track_id: ObjectPath<'_>, _position: i64) {}
+
+    async fn open_uri(&self, _uri: String) {}
+
+    #[zbus(property)]
+    async fn playback_status(&self) -> String {
+        let status = self.shared_state.snapshot();
+        if status.is_active {
+            "Playing"
+        } else if status.time_left == status.duration {
+            "Stopped"
+        } else {
+            "Paused"
+        }
+        .to_string()
+    }
+
+    #[zbus(property)]
+    async fn rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    async fn minimum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    async fn maximum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    async fn metadata(&self) -> HashMap<String, OwnedValue> {
+        metadata(
+            &self.shared_state.snapshot(),
+            self.extended.load(Ordering::SeqCst),
+        )
+    }
+
+    #[zbus(property)]
+    async fn volume(&self) -> f64 {
+        1.0
+    }
+
+    /// Geçen süre (mikro saniye)
+    #[zbus(property)]
+    async fn position(&self) -> i64 {
+        position_us(
+            &self.shared_state.snapshot(),
+            self.extended.load(Ordering::SeqCst),
+        )
+    }
+
+    #[zbus(property)]
+    async fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    async fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    async fn can_play(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    async fn can_pause(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    async fn can_seek(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    async fn can_control(&self) -> bool {
+        true
+    }
+}
+
+/// Durum değişikliklerini PropertiesChanged olarak yayınla
+async fn emit_changes(conn: &Connection) -> zbus::Result<()> {
+    let iface = conn
+        .object_server()
+        .interface::<_, MprisPlayer>(MPRIS_PATH)
+        .await?;
+    let ctx: &SignalContext<'_> = iface.signal_context();
+    let player = iface.get().await;
+    player.playback_status_changed(ctx).await?;
+    player.metadata_changed(ctx).await?;
+    Ok(())
+}
+
+/// MPRIS nesnelerini mevcut D-Bus bağlantısına ekle ve adı talep et
+pub async fn serve(
+    conn: &Connection,
+    app_handle: AppHandle,
+    shared_state: Arc<SharedTimerState>,
+) -> zbus::Result<()> {
+    let mut events = shared_state.subscribe();
+    let extended = Arc::new(AtomicU32::new(0));
+
+    let object_server = conn.object_server();
+    object_server
+        .at(
+            MPRIS_PATH,
+            MprisRoot {
+                app_handle: app_handle.clone(),
+            },
+        )
+        .await?;
+    object_server
+        .at(
+            MPRIS_PATH,
+            MprisPlayer {
+                shared_state,
+                app_handle,
+                extended: extended.clone(),
+            },
+        )
+        .await?;
+    conn.request_name(MPRIS_NAME).await?;
+
+    let conn = conn.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(transition) => {
+                    match transition.event {
+                        TimerEvent::Extended { seconds } => {
+                            extended.fetch_add(seconds, Ordering::SeqCst);
+                        }
+                        // Yeni oturum veya baştan başlama: uzatmalar sıfırlanır
+                        TimerEvent::Started
+                        | TimerEvent::Reset
+                        | TimerEvent::Finished { .. }
+                        | TimerEvent::Skipped { .. } => extended.store(0, Ordering::SeqCst),
+                        TimerEvent::Paused | TimerEvent::Resumed => {}
+                    }
+                    if let Err(e) = emit_changes(&conn).await {
+                        eprintln!("MPRIS: özellik değişikliği yayınlanamadı: {}", e);
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(time_left: u32) -> TimerStatus {
+        TimerStatus {
+            time_left,
+            duration: 1500,
+            ..TimerStatus::default()
+        }
+    }
+
+    #[test]
+    fn position_counts_elapsed_time() {
+        assert_eq!(position_us(&status(1500), 0), 0);
+        assert_eq!(position_us(&status(1400), 0), 100 * 1_000_000);
+    }
+
+    #[test]
+    fn extension_grows_length_and_keeps_position() {
+        // 100 saniye sonra 60 saniye eklendi
+        let extended = status(1460);
+        assert_eq!(session_length(&extended, 60), 1560);
+        assert_eq!(position_us(&extended, 60), 100 * 1_000_000);
+    }
+
+    #[test]
+    fn missed_extension_is_clamped() {
+        // Uzatma olayı kaçırıldıysa uzunluk kalan süreye göre büyür
+        assert_eq!(session_length(&status(1600), 0), 1600);
+        assert_eq!(position_us(&status(1600), 0), 0);
+    }
+}
@@ -0,0 +1,408 @@
+//! Bildirim modülü.
+//! Oturum geçişlerinde org.freedesktop.Notifications üzerinden eylem düğmeli
+//! bildirimler gönderir; ActionInvoked sinyalleri timer komutlarına çevrilir.
+//! Tüm bildirimler aynı kimliği (replaces_id) kullanır ve yerinde güncellenir.
+//! Bitiş ve uyarı zamanları Rust'ta zamanlanır: webview askıya alınıp bitişi
+//! bildiremese de oturum sonu bildirimi gösterilir.
+
+use crate::control::TimerCommand;
+use crate::dbus::{SharedTimerState, TimerStatus};
+use crate::events::{TimerEvent, TimerTransition};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use zbus::export::futures_util::StreamExt;
+use zbus::zvariant::Value;
+use zbus::Connection;
+
+const EXTEND_SECONDS: u32 = 5 * 60;
+
+/// Arayüz bitişi bu süre içinde bildirmezse bildirim Rust'tan gönderilir
+const END_GRACE_SECS: u64 = 3;
+
+const URGENCY_NORMAL: u8 = 1;
+const URGENCY_CRITICAL: u8 = 2;
+
+/// Gönderilecek bildirimin içeriği
+struct Notification {
+    body: String,
+    actions: Vec<(&'static str, &'static str)>,
+    urgency: u8,
+}
+
+impl Notification {
+    /// Oturum bitişi bildirimi (yalnızca süre dolduğunda)
+    fn for_transition(transition: &TimerTransition) -> Option<Self> {
+        let TimerEvent::Finished { from, to } = &transition.event else {
+            return None;
+        };
+
+        let mut actions = Vec::new();
+        let body = if from != "work" {
+            if !transition.status.is_active {
+                actions.push(("start", "Sonraki Pomodoroyu Başlat"));
+            }
+            "Mola bitti, çalışmaya devam!"
+        } else {
+            if !transition.status.is_active {
+                actions.push(("start", "Molayı Başlat"));
+            }
+            actions.push(("skip", "Molayı Atla"));
+            if to == "longBreak" {
+                "Harika iş! Uzun mola zamanı."
+            } else {
+                "İyi çalışma! Kısa mola zamanı."
+            }
+        };
+
+        Some(Self {
+            body: body.to_string(),
+            actions,
+            urgency: URGENCY_CRITICAL,
+        })
+    }
+
+    /// Arayüzden haber gelmeden süresi dolan oturumun bildirimi
+    fn ended(status: &TimerStatus) -> Self {
+        let body = if status.state == "work" {
+            "Odaklanma süresi doldu, mola zamanı."
+        } else {
+            "Mola bitti, çalışmaya devam!"
+        };
+        Self {
+            body: body.to_string(),
+            actions: Vec::new(),
+            urgency: URGENCY_CRITICAL,
+        }
+    }
+
+    /// Oturum bitmeden önceki uyarı bildirimi
+    fn warning(status: &TimerStatus) -> Self {
+        let mins = status.time_left / 60;
+        let secs = status.time_left % 60;
+        let what = if status.state == "work" { "Odaklanma" } else { "Mola" };
+        let body = if mins > 0 && secs == 0 {
+            format!("{} {} dakika içinde bitiyor.", what, mins)
+        } else if mins > 0 {
+            format!("{} {} dakika {} saniye içinde bitiyor.", what, mins, secs)
+        } else {
+            format!("{} {} saniye içinde bitiyor.", what, secs)
+        };
+
+        Self {
+            body,
+            actions: vec![("extend", "+5 dk")],
+            urgency: URGENCY_NORMAL,
+        }
+    }
+}
+
+/// Bildirim eylem anahtarını timer komutuna çevir
+fn action_command(key: &str) -> Option<TimerCommand> {
+    match key {
+        "start" => Some(TimerCommand::Start),
+        "skip" => Some(TimerCommand::Skip),
+        "extend" => Some(TimerCommand::Extend(EXTEND_SECONDS)),
+        _ => None,
+    }
+}
+
+/// Uyarının ne zaman gösterileceğini hesapla
+fn warning_deadline(status: &TimerStatus, warning_secs: u32) -> Option<Instant> {
+    if !status.is_active || warning_secs == 0 || status.time_left <= warning_secs {
+        return None;
+    }
+    let wait = status.time_left - warning_secs;
+    Some(Instant::now() + Duration::from_secs(wait as u64))
+}
+
+/// Aktif oturumun süresinin dolacağı an (arayüze tanınan pay dahil)
+fn end_deadline(status: &TimerStatus) -> Option<Instant> {
+    if !status.is_active {
+        return None;
+    }
+    let wait = status.time_left as u64 + END_GRACE_SECS;
+    Some(Instant::now() + Duration::from_secs(wait))
+}
+
+/// Rust'tan gönderilmiş bitiş bildirimini sonlandıran geçiş mi: bildirimin yerini
+/// tuttuğu Finished, ya da oturumu bitirmeden kapatan atlama/sıfırlama.
+/// Arada gelen duraklatma, uzatma vb. bayrağı temizlemez.
+fn settles_end_notice(event: &TimerEvent) -> bool {
+    matches!(
+        event,
+        TimerEvent::Finished { .. } | TimerEvent::Skipped { .. } | TimerEvent::Reset
+    )
+}
+
+async fn send(proxy: &zbus::Proxy<'_>, last_id: &AtomicU32, notification: Notification) {
+    let mut actions = Vec::with_capacity(notification.actions.len() * 2);
+    for (key, label) in &notification.actions {
+        actions.push(*key);
+        actions.push(*label);
+    }
+
+    let mut hints: HashMap<&str, Value<'_>> = HashMap::new();
+    hints.insert("urgency", Value::U8(notification.urgency));
+    hints.insert("desktop-entry", Value::from("pomodoro-tauri"));
+
+    let result = proxy
+        .call::<_, _, u32>(
+            "Notify",
+            &(
+                "Pomodoro",
+                last_id.load(Ordering::SeqCst),
+                "pomodoro-tauri",
+                "Pomodoro",
+                notification.body.as_str(),
+                actions,
+                hints,
+                -1i32,
+            ),
+        )
+        .await;
+
+    match result {
+        Ok(id) => last_id.store(id, Ordering::SeqCst),
+        Err(e) => eprintln!("Bildirim gönderilemedi: {}", e),
+    }
+}
+
+pub struct Notifier {
+    warning_secs: Arc<AtomicU32>,
+    /// Uyarı süresi değişti; zamanlama mevcut durumdan yeniden hesaplanır
+    changed: Arc<Notify>,
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        Self {
+            warning_secs: Arc::new(AtomicU32::new(0)),
+            changed: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Oturum bitiminden kaç saniye önce uyarı gösterileceği (0 = kapalı)
+    pub fn set_warning_seconds(&self, seconds: u32) {
+        if self.warning_secs.swap(seconds, Ordering::SeqCst) != seconds {
+            self.changed.notify_one();
+        }
+    }
+
+    /// Arka plan görevi olarak bildirimleri başlat
+    pub fn start(&self, app_handle: AppHandle, shared_state: Arc<SharedTimerState>) {
+        let warning_secs = self.warning_secs.clone();
+        let changed = self.changed.clone();
+        let mut events = shared_state.subscribe();
+
+        tauri::async_runtime::spawn(async move {
+            let conn = match Connection::session().await {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("Bildirimler: D-Bus bağlantısı kurulamadı: {}", e);
+                    return;
+                }
+            };
+
+            let proxy: zbus::Proxy<'static> = match zbus::proxy::Builder::new(&conn)
+                .destination("org.freedesktop.Notifications")
+                .expect("valid destination")
+                .path("/org/freedesktop/Notifications")
+                .expect("valid path")
+                .interface("org.freedesktop.Notifications")
+                .expect("valid interface")
+                .build()
+                .await
+            {
+                Ok(p) => p,
+                Err(e) => {
+                    eprintln!("Bildirimler: Proxy oluşturulamadı: {}", e);
+                    return;
+                }
+            };
+
+            let last_id = Arc::new(AtomicU32::new(0));
+
+            // Bildirim düğmelerine basıldığında timer'ı yönet
+            match proxy.receive_signal("ActionInvoked").await {
+                Ok(mut stream) => {
+                    let last_id = last_id.clone();
+                    tauri::async_runtime::spawn(async move {
+                        while let Some(msg) = stream.next().await {
+                            let Ok((id, key)) = msg.body().deserialize::<(u32, String)>() else {
+                                continue;
+                            };
+                            if id != last_id.load(Ordering::SeqCst) {
+                                continue;
+                            }
+                            if let Some(command) = action_command(&key) {
+                                command.dispatch(&app_handle);
+                            }
+                        }
+                    });
+                }
+                Err(e) => eprintln!("Bildirimler: ActionInvoked dinlenemiyor: {}", e),
+            }
+
+            let mut warn_at: Option<Instant> = None;
+            let mut end_at: Option<Instant> = None;
+            // Bitiş bildirimi arayüzden önce Rust tarafından gönderildi
+            let mut end_notified = false;
+
+            loop {
+                let sleep = |deadline: Option<Instant>| async move {
+                    match deadline {
+                        Some(at) => tokio::time::sleep_until(at).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                };
+
+                tokio::select! {
+                    received = events.recv() => match received {
+                        Ok(transition) => {
+                            warn_at = warning_deadline(
+                                &transition.status,
+                                warning_secs.load(Ordering::SeqCst),
+                            );
+                            end_at = end_deadline(&transition.status);
+                            let already_sent = end_notified;
+                            if settles_end_notice(&transition.event) {
+                                end_notified = false;
+                            }
+                            if let Some(n) = Notification::for_transition(&transition) {
+                                if !already_sent {
+                                    send(&proxy, &last_id, n).await;
+                                }
+                            }
+                        }
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = changed.notified() => {
+                        warn_at = warning_deadline(
+                            &shared_state.snapshot(),
+                            warning_secs.load(Ordering::SeqCst),
+                        );
+                    }
+                    _ = sleep(warn_at) => {
+                        warn_at = None;
+                        let status = shared_state.snapshot();
+                        if status.is_active {
+                            send(&proxy, &last_id, Notification::warning(&status)).await;
+                        }
+                    }
+                    _ = sleep(end_at) => {
+                        end_at = None;
+                        // Arayüz süresi dolan oturumu hâlâ bildirmediyse (askıya alınmış webview)
+                        let status = shared_state.snapshot();
+                        if status.is_active {
+                            send(&proxy, &last_id, Notification::ended(&status)).await;
+                            end_notified = true;
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(state: &str, time_left: u32, is_active: bool) -> TimerStatus {
+        TimerStatus {
+            state: state.to_string(),
+            time_left,
+            is_active,
+            sessions_completed: 0,
+            duration: 25 * 60,
+        }
+    }
+
+    fn finished(from: &str, to: &str, is_active: bool) -> TimerTransition {
+        TimerTransition {
+            event: TimerEvent::Finished {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+            status: status(to, 300, is_active),
+        }
+    }
+
+    /// `deadline` şu andan yaklaşık `secs` saniye sonra mı
+    fn is_in(deadline: Option<Instant>, secs: u64) -> bool {
+        let Some(deadline) = deadline else {
+            return false;
+        };
+        let wait = deadline.saturating_duration_since(Instant::now());
+        wait <= Duration::from_secs(secs) && wait + Duration::from_secs(1) > Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn warning_deadline_needs_active_session_and_time() {
+        assert!(is_in(warning_deadline(&status("work", 600, true), 60), 540));
+        assert_eq!(warning_deadline(&status("work", 600, false), 60), None);
+        assert_eq!(warning_deadline(&status("work", 600, true), 0), None);
+        // Kalan süre uyarı süresinden kısaysa uyarı atlanır
+        assert_eq!(warning_deadline(&status("work", 60, true), 60), None);
+        assert_eq!(warning_deadline(&status("work", 30, true), 60), None);
+    }
+
+    #[test]
+    fn end_deadline_includes_grace() {
+        assert!(is_in(end_deadline(&status("work", 100, true)), 100 + END_GRACE_SECS));
+        assert!(is_in(end_deadline(&status("work", 0, true)), END_GRACE_SECS));
+        assert_eq!(end_deadline(&status("work", 100, false)), None);
+    }
+
+    #[test]
+    fn maps_action_keys() {
+        assert_eq!(action_command("start"), Some(TimerCommand::Start));
+        assert_eq!(action_command("skip"), Some(TimerCommand::Skip));
+        assert_eq!(action_command("extend"), Some(TimerCommand::Extend(EXTEND_SECONDS)));
+        assert_eq!(action_command("default"), None);
+    }
+
+    #[test]
+    fn notifies_only_finished_sessions() {
+        let n = Notification::for_transition(&finished("work", "shortBreak", false)).unwrap();
+        assert_eq!(n.body, "İyi çalışma! Kısa mola zamanı.");
+        assert_eq!(n.actions.iter().map(|(k, _)| *k).collect::<Vec<_>>(), ["start", "skip"]);
+
+        // Otomatik başlayan molada başlatma düğmesi yok
+        let n = Notification::for_transition(&finished("work", "longBreak", true)).unwrap();
+        assert_eq!(n.body, "Harika iş! Uzun mola zamanı.");
+        assert_eq!(n.actions.iter().map(|(k, _)| *k).collect::<Vec<_>>(), ["skip"]);
+
+        let n = Notification::for_transition(&finished("shortBreak", "work", false)).unwrap();
+        assert_eq!(n.body, "Mola bitti, çalışmaya devam!");
+        assert_eq!(n.actions.iter().map(|(k, _)| *k).collect::<Vec<_>>(), ["start"]);
+
+        let skipped = TimerTransition {
+            event: TimerEvent::Skipped {
+                from: "work".to_string(),
+                to: "shortBreak".to_string(),
+            },
+            status: status("shortBreak", 300, false),
+        };
+        assert!(Notification::for_transition(&skipped).is_none());
+    }
+
+    #[test]
+    fn end_notice_is_settled_by_the_session_end_only() {
+        assert!(settles_end_notice(&finished("work", "shortBreak", false).event));
+        assert!(settles_end_notice(&TimerEvent::Reset));
+        assert!(settles_end_notice(&TimerEvent::Skipped {
+            from: "work".to_string(),
+            to: "shortBreak".to_string(),
+        }));
+        assert!(!settles_end_notice(&TimerEvent::Paused));
+        assert!(!settles_end_notice(&TimerEvent::Extended { seconds: 60 }));
+    }
+}
@@ -0,0 +1,310 @@
+//! XDG GlobalShortcuts portalı (org.freedesktop.portal.GlobalShortcuts).
+//! Wayland'de X11 tuş yakalama yalnızca XWayland pencereleri odaktayken çalışır;
+//! portal ise kısayolu masaüstü ortamına kaydettirir. Her tablo değişikliğinde
+//! eski oturum kapatılır ve yeni bir oturumda kısayollar yeniden bağlanır.
+//! Masaüstü ortamı kullanıcıya onay sorabilir veya farklı bir tuş atayabilir.
+
+use crate::shortcuts::ShortcutAction;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+use zbus::export::futures_util::StreamExt;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+use zbus::Connection;
+
+const PORTAL_DEST: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const INTERFACE: &str = "org.freedesktop.portal.GlobalShortcuts";
+
+/// Portal isteği kullanıcı tarafından iptal edildi
+const RESPONSE_CANCELLED: u32 = 1;
+
+/// Response sinyali için en uzun bekleme. Masaüstü ortamı onay penceresi
+/// gösterebildiğinden kısa tutulmaz; süre dolarsa eklentiye dönülür.
+const RESPONSE_TIMEOUT_SECS: u64 = 120;
+
+/// "ctrl+alt+p" → "CTRL+ALT+p" (XDG kısayol biçimi)
+fn preferred_trigger(accelerator: &str) -> String {
+    accelerator
+        .split('+')
+        .map(|part| {
+            let part = part.trim().to_lowercase();
+            match part.as_str() {
+                "ctrl" | "control" => "CTRL".to_string(),
+                "alt" | "option" => "ALT".to_string(),
+                "shift" => "SHIFT".to_string(),
+                "super" | "meta" | "cmd" | "command" => "LOGO".to_string(),
+                // İşlev tuşları keysym adlarında büyük harfle yazılır (F5)
+                key if key.len() > 1 && key.starts_with('f') && key[1..].parse::<u8>().is_ok() => {
+                    key.to_uppercase()
+                }
+                key => key.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+pub struct GlobalShortcutsPortal {
+    conn: Connection,
+    proxy: zbus::Proxy<'static>,
+    /// D-Bus benzersiz adından türetilen yol parçası (":1.42" → "1_42")
+    sender: String,
+    /// Açık oturum; Activated sinyalleri yalnızca bu oturum için işlenir
+    session: Arc<Mutex<Option<OwnedObjectPath>>>,
+    counter: u32,
+}
+
+impl GlobalShortcutsPortal {
+    /// Portala bağlan ve tetiklenen kısayolları çalıştır; portal yoksa None
+    pub async fn connect(app_handle: AppHandle) -> Option<Self> {
+        let conn = match Connection::session().await {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Kısayol portalı: D-Bus bağlantısı kurulamadı: {}", e);
+                return None;
+            }
+        };
+        let proxy = match zbus::Proxy::new(&conn, PORTAL_DEST, PORTAL_PATH, INTERFACE).await {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("Kısayol portalı: Proxy oluşturulamadı: {}", e);
+                return None;
+            }
+        };
+        // Arayüz yoksa sürüm özelliği okunamaz
+        if let Err(e) = proxy.get_property::<u32>("version").await {
+            eprintln!("Kısayol portalı kullanılamıyor: {}", e);
+            return None;
+        }
+
+        let sender = conn
+            .unique_name()?
+            .trim_start_matches(':')
+            .replace('.', "_");
+
+        let session: Arc<Mutex<Option<OwnedObjectPath>>> = Arc::new(Mutex::new(None));
+        match proxy.receive_signal("Activated").await {
+            Ok(mut stream) => {
+                let session = session.clone();
+                tauri::async_runtime::spawn(async move {
+                    while let Some(msg) = stream.next().await {
+                        let Ok((path, id, _timestamp, _options)) = msg.body().deserialize::<(
+                            OwnedObjectPath,
+                            String,
+                            u64,
+                            HashMap<String, OwnedValue>,
+                        )>(
+                        ) else {
+                            continue;
+                        };
+                        // Kapatılmış eski oturumlardan gelen sinyaller yok sayılır
+                        let current = match session.lock() {
+                            Ok(s) => s.as_ref() == Some(&path),
+                            Err(e) => {
+                                eprintln!("GlobalShortcutsPortal session mutex poisoned: {}", e);
+                                false
+                            }
+                        };
+                        if !current {
+                            continue;
+                        }
+                        if let Some(action) = ShortcutAction::from_id(&id) {
+                            action.run(&app_handle);
+                        }
+                    }
+                });
+            }
+            Err(e) => {
+                eprintln!("Kısayol portalı: Activated dinlenemiyor: {}", e);
+                return None;
+            }
+        }
+
+        Some(Self {
+            conn,
+            proxy,
+            sender,
+            session,
+            counter: 0,
+        })
+    }
+
+    fn next_token(&mut self) -> String {
+        self.counter += 1;
+        format!("pomodoro{}", self.counter)
+    }
+
+    fn set_session(&self, session: Option<OwnedObjectPath>) -> Option<OwnedObjectPath> {
+        match self.session.lock() {
+            Ok(mut s) => std::mem::replace(&mut *s, session),
+            Err(e) => {
+                eprintln!("GlobalShortcutsPortal session mutex poisoned: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Portal yöntemini çağır ve Request nesnesinin Response sinyalini bekle
+    /// (en fazla RESPONSE_TIMEOUT_SECS; süre dolarsa istek kapatılır ve hata döner)
+    async fn request<B>(
+        &mut self,
+        method: &str,
+        token: &str,
+        body: &B,
+    ) -> zbus::Result<(u32, HashMap<String, OwnedValue>)>
+    where
+        B: serde::Serialize + zbus::zvariant::DynamicType,
+    {
+        // Yanıtı kaçırmamak için sinyale yöntem çağrısından önce abone olunur
+        let path = format!("{}/request/{}/{}", PORTAL_PATH, self.sender, token);
+        let request = zbus::Proxy::new(
+            &self.conn,
+            PORTAL_DEST,
+            path,
+            "org.freedesktop.portal.Request",
+        )
+        .await?;
+        let mut responses = request.receive_signal("Response").await?;
+
+        self.proxy.call_method(method, body).await?;
+
+        let response =
+            tokio::time::timeout(Duration::from_secs(RESPONSE_TIMEOUT_SECS), responses.next())
+                .await;
+        let msg = match response {
+            Ok(Some(msg)) => msg,
+            Ok(None) => return Err(zbus::Error::Failure("Portal yanıt vermedi".to_string())),
+            Err(_) => {
+                // Açık kalan onay penceresi varsa kapatılsın
+                let _ = request.call_method("Close", &()).await;
+                return Err(zbus::Error::Failure(format!(
+                    "Portal {} saniye içinde yanıt vermedi",
+                    RESPONSE_TIMEOUT_SECS
+                )));
+            }
+        };
+        msg.body().deserialize()
+    }
+
+    async fn close_session(&mut self) {
+        let Some(session) = self.set_session(None) else {
+            return;
+        };
+        let result = async {
+            zbus::Proxy::new(
+                &self.conn,
+                PORTAL_DEST,
+                session,
+                "org.freedesktop.portal.Session",
+            )
+            .await?
+            .call_method("Close", &())
+            .await
+        }
+        .await;
+        if let Err(e) = result {
+            eprintln!("Kısayol portalı: oturum kapatılamadı: {}", e);
+        }
+    }
+
+    /// Tabloyu yeni bir oturumda bağla; bağlanan eylemler döner
+    pub async fn bind(
+        &mut self,
+        shortcuts: &[(ShortcutAction, String)],
+    ) -> zbus::Result<Vec<ShortcutAction>> {
+        self.close_session().await;
+        if shortcuts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let token = self.next_token();
+        let session_token = self.next_token();
+        let options = HashMap::from([
+            ("handle_token", Value::from(token.as_str())),
+            ("session_handle_token", Value::from(session_token.as_str())),
+        ]);
+        let (code, _) = self.request("CreateSession", &token, &(options,)).await?;
+        if code != 0 {
+            return Err(zbus::Error::Failure(format!(
+                "Portal oturumu açılamadı (yanıt {})",
+                code
+            )));
+        }
+        let session = OwnedObjectPath::try_from(format!(
+            "{}/session/{}/{}",
+            PORTAL_PATH, self.sender, session_token
+        ))?;
+        self.set_session(Some(session.clone()));
+
+        let entries: Vec<(&str, HashMap<&str, Value>)> = shortcuts
+            .iter()
+            .map(|(action, accelerator)| {
+                (
+                    action.id(),
+                    HashMap::from([
+                        ("description", Value::from(action.label())),
+                        (
+                            "preferred_trigger",
+                            Value::from(preferred_trigger(accelerator)),
+                        ),
+                    ]),
+                )
+            })
+            .collect();
+        let token = self.next_token();
+        let options = HashMap::from([("handle_token", Value::from(token.as_str()))]);
+        let result = self
+            .request("BindShortcuts", &token, &(&session, entries, "", options))
+            .await;
+
+        // Bağlanamayan oturum kapatılır; eklentiye dönülünce kısayollar iki kez tetiklenmesin
+        let (code, results) = match result {
+            Ok(response) => response,
+            Err(e) => {
+                self.close_session().await;
+                return Err(e);
+            }
+        };
+        match code {
+            0 => {}
+            RESPONSE_CANCELLED => return Ok(Vec::new()),
+            _ => {
+                self.close_session().await;
+                return Err(zbus::Error::Failure(format!(
+                    "Kısayollar bağlanamadı (yanıt {})",
+                    code
+                )));
+            }
+        }
+
+        // results["shortcuts"]: a(sa{sv}), yalnızca kimlikler gerekli
+        let mut bound = Vec::new();
+        if let Some(Value::Array(array)) = results.get("shortcuts").map(|v| &**v) {
+            for item in array.iter() {
+                if let Value::Structure(entry) = item {
+                    if let Some(Value::Str(id)) = entry.fields().first() {
+                        bound.extend(ShortcutAction::from_id(id.as_str()));
+                    }
+                }
+            }
+        }
+        Ok(bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_accelerators_to_xdg_triggers() {
+        assert_eq!(preferred_trigger("ctrl+alt+p"), "CTRL+ALT+p");
+        assert_eq!(preferred_trigger("Control + Shift + B"), "CTRL+SHIFT+b");
+        assert_eq!(preferred_trigger("super+f5"), "LOGO+F5");
+        assert_eq!(preferred_trigger("cmd+option+space"), "LOGO+ALT+space");
+        // Tek harfli "f" işlev tuşu değildir
+        assert_eq!(preferred_trigger("alt+f"), "ALT+f");
+    }
+}
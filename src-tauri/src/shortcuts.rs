@@ -0,0 +1,515 @@
+//! Ayarlanabilir global kısayollar.
+//! Her timer eylemine (başlat/duraklat, atla, sıfırla, uzat, pencereyi göster,
+//! molayı başlat) bir tuş kombinasyonu atanabilir. Tablo çalışma anında yeniden
+//! kaydedilir; geçersiz, yinelenen veya başka bir uygulamanın tuttuğu kısayollar
+//! kurulumu durdurmak yerine çakışma olarak arayüze bildirilir.
+//! Wayland oturumlarında kısayollar XDG GlobalShortcuts portalı üzerinden
+//! bağlanır (bkz. portal.rs); portal yoksa veya hata verirse global kısayol
+//! eklentisine (X11 tuş yakalama) dönülür.
+
+use crate::control::{self, TimerCommand};
+use crate::display;
+use crate::portal::GlobalShortcutsPortal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+use tokio::sync::mpsc;
+
+/// Kısayol uzatma eyleminin eklediği süre
+const EXTEND_SECONDS: u32 = 60;
+
+/// Portala bağlanmak üzere gönderilen tablo: eylem ve tuş kombinasyonu
+type PortalTable = Vec<(ShortcutAction, String)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShortcutAction {
+    Toggle,
+    Skip,
+    Reset,
+    Extend,
+    ShowWindow,
+    StartBreak,
+}
+
+impl ShortcutAction {
+    const ALL: [ShortcutAction; 6] = [
+        ShortcutAction::Toggle,
+        ShortcutAction::Skip,
+        ShortcutAction::Reset,
+        ShortcutAction::Extend,
+        ShortcutAction::ShowWindow,
+        ShortcutAction::StartBreak,
+    ];
+
+    /// Portalda kısayol kimliği olarak kullanılan ad (ayarlardaki anahtarla aynı)
+    pub fn id(self) -> &'static str {
+        match self {
+            ShortcutAction::Toggle => "toggle",
+            ShortcutAction::Skip => "skip",
+            ShortcutAction::Reset => "reset",
+            ShortcutAction::Extend => "extend",
+            ShortcutAction::ShowWindow => "showWindow",
+            ShortcutAction::StartBreak => "startBreak",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ShortcutAction::Toggle => "Başlat/Duraklat",
+            ShortcutAction::Skip => "Atla",
+            ShortcutAction::Reset => "Sıfırla",
+            ShortcutAction::Extend => "Uzat",
+            ShortcutAction::ShowWindow => "Pencereyi Göster",
+            ShortcutAction::StartBreak => "Molayı Başlat",
+        }
+    }
+
+    pub fn run(self, app_handle: &AppHandle) {
+        match self {
+            ShortcutAction::Toggle => TimerCommand::Toggle.dispatch(app_handle),
+            ShortcutAction::Skip => TimerCommand::Skip.dispatch(app_handle),
+            ShortcutAction::Reset => TimerCommand::Reset.dispatch(app_handle),
+            ShortcutAction::Extend => TimerCommand::Extend(EXTEND_SECONDS).dispatch(app_handle),
+            ShortcutAction::StartBreak => TimerCommand::StartBreak.dispatch(app_handle),
+            ShortcutAction::ShowWindow => control::show_window(app_handle),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictKind {
+    /// Tuş kombinasyonu çözümlenemedi
+    Invalid,
+    /// Aynı kombinasyon tabloda başka bir eyleme atanmış
+    Duplicate,
+    /// Kombinasyon kaydedilemedi (genellikle başka bir uygulama tutuyor)
+    Unavailable,
+}
+
+/// Kaydedilemeyen bir kısayol
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutConflict {
+    pub action: ShortcutAction,
+    pub accelerator: String,
+    pub kind: ConflictKind,
+    pub message: String,
+}
+
+impl ShortcutConflict {
+    fn new(action: ShortcutAction, accelerator: &str, kind: ConflictKind, message: String) -> Self {
+        Self {
+            action,
+            accelerator: accelerator.to_string(),
+            kind,
+            message,
+        }
+    }
+}
+
+/// Varsayılan tablo: yalnızca başlat/duraklat atanmış
+pub fn default_bindings() -> HashMap<ShortcutAction, String> {
+    HashMap::from([(ShortcutAction::Toggle, "ctrl+alt+p".to_string())])
+}
+
+/// Tabloyu çözümle; boş değerler atanmamış sayılır, geçersiz ve yinelenenler elenir
+fn validate(
+    requested: &HashMap<ShortcutAction, String>,
+) -> (
+    Vec<(ShortcutAction, String, Shortcut)>,
+    Vec<ShortcutConflict>,
+) {
+    let mut valid: Vec<(ShortcutAction, String, Shortcut)> = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for action in ShortcutAction::ALL {
+        let Some(accelerator) = requested
+            .get(&action)
+            .map(|a| a.trim())
+            .filter(|a| !a.is_empty())
+        else {
+            continue;
+        };
+
+        let shortcut: Shortcut = match accelerator.parse() {
+            Ok(s) => s,
+            Err(e) => {
+                conflicts.push(ShortcutConflict::new(
+                    action,
+                    accelerator,
+                    ConflictKind::Invalid,
+                    format!("Geçersiz kısayol: {}", e),
+                ));
+                continue;
+            }
+        };
+        if let Some((other, _, _)) = valid.iter().find(|(_, _, s)| *s == shortcut) {
+            conflicts.push(ShortcutConflict::new(
+                action,
+                accelerator,
+                ConflictKind::Duplicate,
+                format!("\"{}\" ile aynı kısayol", other.label()),
+            ));
+            continue;
+        }
+        valid.push((action, accelerator.to_string(), shortcut));
+    }
+
+    (valid, conflicts)
+}
+
+/// Yeni tabloyu eski kayıtların üzerine kur. Zaten kayıtlı kısayollar yeniden
+/// kaydedilmez, yalnızca eylemleri güncellenir; yeni tuşu kaydedilemeyen bir
+/// eylem, eski tuşu başka bir eyleme geçmediyse onu korur. Dönen tabloda
+/// olmayan eski kısayollar çağıran tarafından bırakılır.
+fn rebind(
+    previous: &HashMap<Shortcut, ShortcutAction>,
+    valid: &[(ShortcutAction, String, Shortcut)],
+    mut register: impl FnMut(Shortcut) -> Result<(), String>,
+) -> (HashMap<Shortcut, ShortcutAction>, Vec<ShortcutConflict>) {
+    let mut bindings = HashMap::new();
+    let mut conflicts = Vec::new();
+    let mut failed = Vec::new();
+
+    for (action, accelerator, shortcut) in valid {
+        if previous.contains_key(shortcut) {
+            bindings.insert(*shortcut, *action);
+            continue;
+        }
+        match register(*shortcut) {
+            Ok(()) => {
+                bindings.insert(*shortcut, *action);
+            }
+            Err(e) => {
+                failed.push(*action);
+                conflicts.push(ShortcutConflict::new(
+                    *action,
+                    accelerator,
+                    ConflictKind::Unavailable,
+                    format!(
+                        "Kısayol kaydedilemedi, başka bir uygulama kullanıyor olabilir: {}",
+                        e
+                    ),
+                ));
+            }
+        }
+    }
+
+    for (shortcut, action) in previous {
+        if failed.contains(action) && !bindings.contains_key(shortcut) {
+            bindings.insert(*shortcut, *action);
+        }
+    }
+
+    (bindings, conflicts)
+}
+
+fn log_conflicts(conflicts: &[ShortcutConflict]) {
+    for c in conflicts {
+        eprintln!(
+            "{} kısayolu ({}): {}",
+            c.action.label(),
+            c.accelerator,
+            c.message
+        );
+    }
+}
+
+pub struct ShortcutManager {
+    /// Eklentiye kaydedilmiş kısayollar (portal kullanılırken boş)
+    bindings: Mutex<HashMap<Shortcut, ShortcutAction>>,
+    conflicts: Mutex<Vec<ShortcutConflict>>,
+    /// Son istenen tablo; arka uç değişince yeniden uygulanır
+    requested: Mutex<HashMap<ShortcutAction, String>>,
+    /// Portal görevi (None: global kısayol eklentisi kullanılıyor)
+    portal: Mutex<Option<mpsc::UnboundedSender<PortalTable>>>,
+}
+
+impl ShortcutManager {
+    pub fn new() -> Self {
+        Self {
+            bindings: Mutex::new(HashMap::new()),
+            conflicts: Mutex::new(Vec::new()),
+            requested: Mutex::new(default_bindings()),
+            portal: Mutex::new(None),
+        }
+    }
+
+    /// Global kısayol eklentisinin işleyicisinden çağrılır
+    pub fn handle(&self, app_handle: &AppHandle, shortcut: &Shortcut) {
+        let action = match self.bindings.lock() {
+            Ok(bindings) => bindings.get(shortcut).copied(),
+            Err(e) => {
+                eprintln!("ShortcutManager::handle mutex poisoned: {}", e);
+                return;
+            }
+        };
+        if let Some(action) = action {
+            action.run(app_handle);
+        }
+    }
+
+    /// Son uygulanan tablodaki çakışmalar
+    pub fn conflicts(&self) -> Vec<ShortcutConflict> {
+        self.conflicts.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    fn store_conflicts(&self, conflicts: &[ShortcutConflict]) {
+        match self.conflicts.lock() {
+            Ok(mut stored) => *stored = conflicts.to_vec(),
+            Err(e) => eprintln!("ShortcutManager mutex poisoned: {}", e),
+        }
+    }
+
+    /// Tabloyu etkin arka uçta yeniden kaydet; o ana kadar bilinen çakışmalar döner.
+    /// Portal kullanılırken portalın reddettikleri sonradan "shortcut-conflicts"
+    /// event'i ile bildirilir.
+    pub fn apply(
+        &self,
+        app_handle: &AppHandle,
+        requested: &HashMap<ShortcutAction, String>,
+    ) -> Vec<ShortcutConflict> {
+        match self.requested.lock() {
+            Ok(mut r) => *r = requested.clone(),
+            Err(e) => eprintln!("ShortcutManager::apply mutex poisoned: {}", e),
+        }
+
+        let (valid, mut conflicts) = validate(requested);
+
+        let portal = self.portal.lock().ok().and_then(|p| p.clone());
+        let sent = portal.is_some_and(|tx| {
+            tx.send(
+                valid
+                    .iter()
+                    .map(|(a, accel, _)| (*a, accel.clone()))
+                    .collect(),
+            )
+            .is_ok()
+        });
+        if sent {
+            self.unregister_plugin(app_handle);
+        } else {
+            conflicts.extend(self.register_plugin(app_handle, &valid));
+        }
+
+        log_conflicts(&conflicts);
+        self.store_conflicts(&conflicts);
+        conflicts
+    }
+
+    fn unregister_plugin(&self, app_handle: &AppHandle) {
+        // Kayıt sırasında kilit tutulmaz; işleyici olay döngüsünde aynı kilidi bekleyebilir
+        let previous = match self.bindings.lock() {
+            Ok(mut b) => std::mem::take(&mut *b),
+            Err(e) => {
+                eprintln!("ShortcutManager mutex poisoned: {}", e);
+                return;
+            }
+        };
+        for shortcut in previous.keys() {
+            if let Err(e) = app_handle.global_shortcut().unregister(*shortcut) {
+                eprintln!("Kısayol kaldırılamadı ({}): {}", shortcut, e);
+            }
+        }
+    }
+
+    fn register_plugin(
+        &self,
+        app_handle: &AppHandle,
+        valid: &[(ShortcutAction, String, Shortcut)],
+    ) -> Vec<ShortcutConflict> {
+        // Kayıt sırasında kilit tutulmaz; işleyici olay döngüsünde aynı kilidi bekleyebilir
+        let previous = match self.bindings.lock() {
+            Ok(b) => b.clone(),
+            Err(e) => {
+                eprintln!("ShortcutManager mutex poisoned: {}", e);
+                HashMap::new()
+            }
+        };
+
+        let (bindings, conflicts) = rebind(&previous, valid, |shortcut| {
+            app_handle
+                .global_shortcut()
+                .register(shortcut)
+                .map_err(|e| e.to_string())
+        });
+        // Yeni tablo kurulduktan sonra yalnızca değişen veya kaldırılan kısayollar bırakılır
+        for shortcut in previous.keys().filter(|s| !bindings.contains_key(s)) {
+            if let Err(e) = app_handle.global_shortcut().unregister(*shortcut) {
+                eprintln!("Kısayol kaldırılamadı ({}): {}", shortcut, e);
+            }
+        }
+
+        match self.bindings.lock() {
+            Ok(mut stored) => *stored = bindings,
+            Err(e) => eprintln!("ShortcutManager mutex poisoned: {}", e),
+        }
+        conflicts
+    }
+
+    /// Arka ucu değiştir ve son istenen tabloyu yeniden uygula
+    fn switch_backend(
+        &self,
+        app_handle: &AppHandle,
+        portal: Option<mpsc::UnboundedSender<PortalTable>>,
+    ) {
+        match self.portal.lock() {
+            Ok(mut p) => *p = portal,
+            Err(e) => eprintln!("ShortcutManager mutex poisoned: {}", e),
+        }
+        let requested = self.requested.lock().map(|r| r.clone()).unwrap_or_default();
+        let conflicts = self.apply(app_handle, &requested);
+        let _ = app_handle.emit("shortcut-conflicts", conflicts);
+    }
+
+    /// Portalın bağlamadığı kısayolları çakışmalara ekle ve arayüze bildir
+    fn portal_bound(
+        &self,
+        app_handle: &AppHandle,
+        requested: &[(ShortcutAction, String)],
+        bound: &[ShortcutAction],
+    ) {
+        let rejected: Vec<ShortcutConflict> = requested
+            .iter()
+            .filter(|(action, _)| !bound.contains(action))
+            .map(|(action, accelerator)| {
+                ShortcutConflict::new(
+                    *action,
+                    accelerator,
+                    ConflictKind::Unavailable,
+                    "Masaüstü ortamı kısayolu bağlamadı".to_string(),
+                )
+            })
+            .collect();
+        log_conflicts(&rejected);
+
+        let mut conflicts = self.conflicts();
+        conflicts.retain(|c| c.kind != ConflictKind::Unavailable);
+        conflicts.extend(rejected);
+        self.store_conflicts(&conflicts);
+        let _ = app_handle.emit("shortcut-conflicts", conflicts);
+    }
+
+    /// Wayland oturumunda portal varsa kısayolları portala taşı.
+    /// `app.manage` ile kaydedildikten sonra çağrılmalıdır.
+    pub fn start(&self, app_handle: AppHandle) {
+        if !display::is_wayland_session() {
+            return;
+        }
+
+        tauri::async_runtime::spawn(async move {
+            let Some(mut portal) = GlobalShortcutsPortal::connect(app_handle.clone()).await else {
+                return;
+            };
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let manager = app_handle.state::<ShortcutManager>();
+            manager.switch_backend(&app_handle, Some(tx));
+
+            while let Some(mut table) = rx.recv().await {
+                // Araya giren tablolar atlanır, yalnızca en yenisi bağlanır
+                while let Ok(newer) = rx.try_recv() {
+                    table = newer;
+                }
+                match portal.bind(&table).await {
+                    Ok(bound) => manager.portal_bound(&app_handle, &table, &bound),
+                    Err(e) => {
+                        eprintln!(
+                            "Kısayol portalı başarısız, global kısayol eklentisine dönülüyor: {}",
+                            e
+                        );
+                        manager.switch_backend(&app_handle, None);
+                        break;
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(entries: &[(ShortcutAction, &str)]) -> HashMap<ShortcutAction, String> {
+        entries.iter().map(|(a, s)| (*a, s.to_string())).collect()
+    }
+
+    fn shortcut(accelerator: &str) -> Shortcut {
+        accelerator.parse().unwrap()
+    }
+
+    #[test]
+    fn validate_skips_empty_and_reports_invalid_and_duplicates() {
+        let (valid, conflicts) = validate(&table(&[
+            (ShortcutAction::Toggle, "ctrl+alt+p"),
+            (ShortcutAction::Skip, "  "),
+            (ShortcutAction::Reset, "ctrl+alt+"),
+            (ShortcutAction::Extend, "alt+ctrl+p"),
+            (ShortcutAction::ShowWindow, " ctrl+alt+w "),
+        ]));
+
+        let actions: Vec<_> = valid.iter().map(|(a, accel, _)| (*a, accel.as_str())).collect();
+        assert_eq!(
+            actions,
+            vec![
+                (ShortcutAction::Toggle, "ctrl+alt+p"),
+                (ShortcutAction::ShowWindow, "ctrl+alt+w"),
+            ]
+        );
+        let kinds: Vec<_> = conflicts.iter().map(|c| (c.action, c.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ShortcutAction::Reset, ConflictKind::Invalid),
+                (ShortcutAction::Extend, ConflictKind::Duplicate),
+            ]
+        );
+    }
+
+    #[test]
+    fn rebind_registers_only_new_shortcuts() {
+        let previous = HashMap::from([(shortcut("ctrl+alt+p"), ShortcutAction::Toggle)]);
+        let (valid, _) = validate(&table(&[
+            (ShortcutAction::Skip, "ctrl+alt+p"),
+            (ShortcutAction::Toggle, "ctrl+alt+t"),
+        ]));
+
+        let mut registered = Vec::new();
+        let (bindings, conflicts) = rebind(&previous, &valid, |s| {
+            registered.push(s);
+            Ok(())
+        });
+
+        assert!(conflicts.is_empty());
+        assert_eq!(registered, vec![shortcut("ctrl+alt+t")]);
+        assert_eq!(bindings[&shortcut("ctrl+alt+p")], ShortcutAction::Skip);
+        assert_eq!(bindings[&shortcut("ctrl+alt+t")], ShortcutAction::Toggle);
+    }
+
+    #[test]
+    fn failed_shortcut_keeps_the_old_binding() {
+        let previous = HashMap::from([
+            (shortcut("ctrl+alt+p"), ShortcutAction::Toggle),
+            (shortcut("ctrl+alt+s"), ShortcutAction::Skip),
+        ]);
+        let (valid, _) = validate(&table(&[(ShortcutAction::Toggle, "ctrl+alt+t")]));
+
+        let (bindings, conflicts) = rebind(&previous, &valid, |_| Err("taken".to_string()));
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, ConflictKind::Unavailable);
+        // Toggle eski tuşunda kalır, tablodan çıkarılan Skip bırakılır
+        assert_eq!(
+            bindings,
+            HashMap::from([(shortcut("ctrl+alt+p"), ShortcutAction::Toggle)])
+        );
+    }
+}
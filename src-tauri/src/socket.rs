@@ -0,0 +1,344 @@
+//! Unix domain socket kontrol protokolü.
+//! Oturum D-Bus'ı olmayan ortamlar (container'lar, SSH oturumları, dbus-launch
+//! kullanmayan pencere yöneticileri) için $XDG_RUNTIME_DIR/pomodoro.sock üzerinden
+//! satır tabanlı JSON ile D-Bus arayüzüyle aynı komutları sunar. XDG_RUNTIME_DIR
+//! tanımlı değilse (çoğu SSH ve container oturumu) soket yalnızca kullanıcıya açık
+//! /tmp/pomodoro-$UID dizininde oluşturulur.
+//!
+//! İstek:  {"command":"extend","seconds":60}
+//! Yanıt:  {"ok":true} / {"ok":true,"status":{...}} / {"ok":false,"error":"..."}
+
+use crate::audio::{AudioPlayer, Channel};
+use crate::control::{self, TimerCommand};
+use crate::dbus::{SharedTimerState, TimerStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+/// Soket dosyası bu süreç tarafından mı oluşturuldu
+static OWNS_SOCKET: AtomicBool = AtomicBool::new(false);
+
+/// Bir istek satırının en fazla uzunluğu (istekler birkaç yüz bayttır)
+const MAX_LINE_BYTES: u64 = 4096;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+pub enum SocketRequest {
+    Toggle,
+    Start,
+    Stop,
+    Skip,
+    Reset,
+    Extend { seconds: u32 },
+    Status,
+    /// Ana pencereyi göster (uygulama ikinci kez başlatıldığında)
+    Show,
+    /// `volume` verilirse kanalı ayarlar, her durumda tüm ses düzeylerini döndürür
+    Volume {
+        #[serde(default)]
+        channel: Option<Channel>,
+        #[serde(default)]
+        volume: Option<f32>,
+    },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SocketResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TimerStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volumes: Option<HashMap<Channel, f32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SocketRequest {
+    fn command(&self) -> Option<TimerCommand> {
+        match self {
+            SocketRequest::Toggle => Some(TimerCommand::Toggle),
+            SocketRequest::Start => Some(TimerCommand::Start),
+            SocketRequest::Stop => Some(TimerCommand::Stop),
+            SocketRequest::Skip => Some(TimerCommand::Skip),
+            SocketRequest::Reset => Some(TimerCommand::Reset),
+            SocketRequest::Extend { seconds } => Some(TimerCommand::Extend(*seconds)),
+            SocketRequest::Status | SocketRequest::Show | SocketRequest::Volume { .. } => None,
+        }
+    }
+}
+
+/// Soket dizini ve XDG_RUNTIME_DIR'dan mı geldiği
+fn socket_dir() -> (PathBuf, bool) {
+    match std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+    {
+        Some(dir) => (dir, true),
+        None => {
+            let uid = unsafe { libc::getuid() };
+            (std::env::temp_dir().join(format!("pomodoro-{}", uid)), false)
+        }
+    }
+}
+
+/// $XDG_RUNTIME_DIR/pomodoro.sock, yoksa /tmp/pomodoro-$UID/pomodoro.sock
+pub fn socket_path() -> PathBuf {
+    socket_dir().0.join("pomodoro.sock")
+}
+
+/// Yedek dizin başka bir kullanıcıya ait veya başkalarına açıksa kullanılmaz
+fn check_private_dir(dir: &Path) -> std::io::Result<()> {
+    let meta = std::fs::symlink_metadata(dir)?;
+    let uid = unsafe { libc::getuid() };
+    if !meta.is_dir() || meta.uid() != uid || meta.mode() & 0o077 != 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("{} is not a private directory", dir.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// Soket dizinini hazırla; yedek dizin yoksa 0700 izniyle oluşturulur
+fn prepare_dir() -> std::io::Result<PathBuf> {
+    let (dir, from_env) = socket_dir();
+    if !from_env {
+        match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+        check_private_dir(&dir)?;
+    }
+    Ok(dir.join("pomodoro.sock"))
+}
+
+/// Çalışan uygulamaya tek bir istek gönder (CLI tarafı)
+pub async fn request(req: &SocketRequest) -> std::io::Result<SocketResponse> {
+    let (dir, from_env) = socket_dir();
+    if !from_env {
+        check_private_dir(&dir)?;
+    }
+    let path = dir.join("pomodoro.sock");
+
+    let mut stream = BufReader::new(UnixStream::connect(path).await?);
+    let mut line = serde_json::to_string(req)?;
+    line.push('\n');
+    stream.get_mut().write_all(line.as_bytes()).await?;
+
+    let mut reply = String::new();
+    stream.read_line(&mut reply).await?;
+    Ok(serde_json::from_str(&reply)?)
+}
+
+async fn handle_line(line: &str, app: &AppHandle, shared_state: &SharedTimerState) -> SocketResponse {
+    let req: SocketRequest = match serde_json::from_str(line) {
+        Ok(r) => r,
+        Err(e) => {
+            return SocketResponse {
+                error: Some(format!("Invalid request: {}", e)),
+                ..Default::default()
+            }
+        }
+    };
+
+    if let Some(command) = req.command() {
+        command.dispatch(app);
+        return SocketResponse {
+            ok: true,
+            ..Default::default()
+        };
+    }
+
+    match req {
+        SocketRequest::Volume {
+            channel: Some(channel),
+            volume: Some(volume),
+        } => control::set_volume(app, channel, volume),
+        SocketRequest::Volume {
+            channel: None,
+            volume: Some(_),
+        } => {
+            return SocketResponse {
+                error: Some("Missing channel".to_string()),
+                ..Default::default()
+            }
+        }
+        SocketRequest::Volume { .. } => {}
+        SocketRequest::Show => {
+            control::show_window(app);
+            return SocketResponse {
+                ok: true,
+                ..Default::default()
+            };
+        }
+        _ => {
+            return SocketResponse {
+                ok: true,
+                status: Some(shared_state.snapshot()),
+                ..Default::default()
+            }
+        }
+    }
+
+    SocketResponse {
+        ok: true,
+        volumes: Some(app.state::<AudioPlayer>().volumes().await),
+        ..Default::default()
+    }
+}
+
+/// Bir istek satırı oku. Ok(false): bağlantı kapandı. Satır MAX_LINE_BYTES'a
+/// ulaşıp bitmediyse InvalidData hatası döner.
+async fn read_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    line: &mut String,
+) -> std::io::Result<bool> {
+    let read = reader.take(MAX_LINE_BYTES).read_line(line).await?;
+    if read as u64 == MAX_LINE_BYTES && !line.ends_with('\n') {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "request line too long",
+        ));
+    }
+    Ok(read > 0)
+}
+
+async fn write_response(
+    stream: &mut BufReader<UnixStream>,
+    response: &SocketResponse,
+) -> std::io::Result<()> {
+    let mut reply = serde_json::to_string(response)?;
+    reply.push('\n');
+    stream.get_mut().write_all(reply.as_bytes()).await
+}
+
+async fn handle_connection(
+    stream: UnixStream,
+    app: AppHandle,
+    shared_state: Arc<SharedTimerState>,
+) -> std::io::Result<()> {
+    let mut stream = BufReader::new(stream);
+    let mut line = String::new();
+
+    // Bir bağlantı üzerinden birden fazla istek gönderilebilir
+    loop {
+        match read_line(&mut stream, &mut line).await {
+            Ok(true) => {}
+            Ok(false) => break,
+            // Satırın geri kalanı okunmadan akış sürdürülemez: yanıtla ve kapat
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                let response = SocketResponse {
+                    error: Some("Request too long".to_string()),
+                    ..Default::default()
+                };
+                return write_response(&mut stream, &response).await;
+            }
+            Err(e) => return Err(e),
+        }
+        if !line.trim().is_empty() {
+            let response = handle_line(line.trim(), &app, &shared_state).await;
+            write_response(&mut stream, &response).await?;
+        }
+        line.clear();
+    }
+    Ok(())
+}
+
+/// Socket sunucusunu arka plan görevi olarak başlat
+pub fn start(app_handle: AppHandle, shared_state: Arc<SharedTimerState>) {
+    let path = match prepare_dir() {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("Kontrol soketi: dizin hazırlanamadı, soket açılmadı: {}", e);
+            return;
+        }
+    };
+
+    tauri::async_runtime::spawn(async move {
+        if path.exists() {
+            // Başka bir örnek dinliyorsa soketi devralma, değilse eski dosyayı sil
+            if UnixStream::connect(&path).await.is_ok() {
+                eprintln!("Kontrol soketi: {} zaten kullanımda", path.display());
+                return;
+            }
+            let _ = std::fs::remove_file(&path);
+        }
+
+        let listener = match UnixListener::bind(&path) {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("Kontrol soketi: {} açılamadı: {}", path.display(), e);
+                return;
+            }
+        };
+        OWNS_SOCKET.store(true, Ordering::SeqCst);
+        // Dizin zaten yalnızca kullanıcıya açık; soket dosyası da 0600 olsun
+        if let Err(e) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+            eprintln!("Kontrol soketi: izinler ayarlanamadı: {}", e);
+        }
+
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    eprintln!("Kontrol soketi: bağlantı kabul edilemedi: {}", e);
+                    continue;
+                }
+            };
+
+            let app = app_handle.clone();
+            let shared_state = shared_state.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = handle_connection(stream, app, shared_state).await {
+                    eprintln!("Kontrol soketi: bağlantı hatası: {}", e);
+                }
+            });
+        }
+    });
+}
+
+/// Uygulama kapanırken soket dosyasını kaldır
+pub fn cleanup() {
+    if !OWNS_SOCKET.load(Ordering::SeqCst) {
+        return;
+    }
+    let _ = std::fs::remove_file(socket_path());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_one_request_per_line() {
+        let mut reader: &[u8] = b"{\"command\":\"toggle\"}\n{\"command\":\"status\"}";
+        let mut line = String::new();
+
+        assert!(read_line(&mut reader, &mut line).await.unwrap());
+        assert_eq!(line, "{\"command\":\"toggle\"}\n");
+        line.clear();
+        // Son satır yeni satırsız bitebilir
+        assert!(read_line(&mut reader, &mut line).await.unwrap());
+        assert_eq!(line, "{\"command\":\"status\"}");
+        line.clear();
+        assert!(!read_line(&mut reader, &mut line).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn rejects_overlong_lines() {
+        let data = vec![b'x'; MAX_LINE_BYTES as usize * 2];
+        let mut reader: &[u8] = &data;
+        let mut line = String::new();
+
+        let err = read_line(&mut reader, &mut line).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(line.len(), MAX_LINE_BYTES as usize);
+    }
+}
@@ -0,0 +1,315 @@
+//! Kullanıcı ses paketleri.
+//! $XDG_DATA_HOME/pomodoro/sounds/<paket>/ (varsayılan ~/.local/share/pomodoro/sounds)
+//! altındaki her dizin bir pakettir. Dizindeki pack.json olayları dosyalara eşler:
+//!
+//! {"name": "Retro", "sounds": {"workEnd": "ding.wav", "breakEnd": "gong.flac",
+//!  "tick": "tick.mp3", "warning": "chime.ogg"}}
+//!
+//! Paketteki her dosya yüklenirken çözülebilirliği denetlenir; bozuk veya
+//! desteklenmeyen dosyalar atlanır ve o olay için paketle gelen ses kullanılır.
+
+use rodio::Decoder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const MANIFEST: &str = "pack.json";
+const SUPPORTED_EXTENSIONS: [&str; 4] = ["ogg", "wav", "flac", "mp3"];
+
+/// Paketlerin ses atayabildiği olaylar
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SoundEvent {
+    /// Çalışma oturumu bitti
+    WorkEnd,
+    /// Mola bitti
+    BreakEnd,
+    /// Çalışma sırasında tıkırtı döngüsü
+    Tick,
+    /// Oturum bitişi öncesi uyarı
+    Warning,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    sounds: HashMap<SoundEvent, PathBuf>,
+}
+
+/// Yüklenmiş bir ses paketi
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoundPack {
+    /// Dizin adı
+    pub id: String,
+    /// Manifest'teki görünen ad (yoksa dizin adı)
+    pub name: String,
+    /// Geçerli dosyası olan olaylar
+    pub events: Vec<SoundEvent>,
+    /// Yükleme sırasında atlanan dosyalar ve nedenleri
+    pub errors: Vec<String>,
+    #[serde(skip)]
+    files: HashMap<SoundEvent, PathBuf>,
+}
+
+/// $XDG_DATA_HOME/pomodoro/sounds (varsayılan ~/.local/share/pomodoro/sounds)
+pub fn packs_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+        })?;
+    Some(base.join("pomodoro").join("sounds"))
+}
+
+/// Dosyanın desteklenen bir biçimde olduğunu ve çözülebildiğini denetle
+fn validate(path: &Path) -> Result<(), String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    if !SUPPORTED_EXTENSIONS.contains(&extension.as_str()) {
+        return Err("unsupported format".to_string());
+    }
+
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    Decoder::new(BufReader::new(file)).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Manifest'teki yolu paket dizinine göre çöz; dizin dışına çıkan yolları reddet
+fn resolve_in(dir: &Path, file: &Path) -> Result<PathBuf, String> {
+    let path = dir.join(file).canonicalize().map_err(|e| e.to_string())?;
+    let dir = dir.canonicalize().map_err(|e| e.to_string())?;
+    if !path.starts_with(&dir) {
+        return Err("path is outside the pack directory".to_string());
+    }
+    Ok(path)
+}
+
+fn load_pack(dir: &Path) -> Option<SoundPack> {
+    let id = dir.file_name()?.to_str()?.to_string();
+    let manifest_path = dir.join(MANIFEST);
+    if !manifest_path.is_file() {
+        return None;
+    }
+
+    let mut pack = SoundPack {
+        name: id.clone(),
+        id,
+        events: Vec::new(),
+        errors: Vec::new(),
+        files: HashMap::new(),
+    };
+
+    let manifest: Manifest = match std::fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| e.to_string()))
+    {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("Ses paketi '{}': {} okunamadı: {}", pack.id, MANIFEST, e);
+            pack.errors.push(format!("{}: {}", MANIFEST, e));
+            return Some(pack);
+        }
+    };
+
+    if let Some(name) = manifest.name.filter(|n| !n.trim().is_empty()) {
+        pack.name = name;
+    }
+
+    for (event, file) in manifest.sounds {
+        match resolve_in(dir, &file).and_then(|path| validate(&path).map(|_| path)) {
+            Ok(path) => {
+                pack.files.insert(event, path);
+            }
+            Err(e) => {
+                eprintln!(
+                    "Ses paketi '{}': {} atlandı: {}",
+                    pack.id,
+                    file.display(),
+                    e
+                );
+                pack.errors.push(format!("{}: {}", file.display(), e));
+            }
+        }
+    }
+
+    pack.events = pack.files.keys().copied().collect();
+    pack.events.sort_by_key(|e| *e as u8);
+    Some(pack)
+}
+
+/// Ses paketleri dizinini tara (dizin adına göre sıralı)
+pub fn scan() -> Vec<SoundPack> {
+    let Some(dir) = packs_dir() else {
+        return Vec::new();
+    };
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(e) => e,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            eprintln!("Ses paketleri okunamadı ({}): {}", dir.display(), e);
+            return Vec::new();
+        }
+    };
+
+    let mut packs: Vec<SoundPack> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| load_pack(&path))
+        .collect();
+    packs.sort_by(|a, b| a.id.cmp(&b.id));
+    packs
+}
+
+/// Yüklü paketler ve seçili paket
+pub struct SoundLibrary {
+    packs: Mutex<Vec<SoundPack>>,
+    active: Mutex<Option<String>>,
+}
+
+impl SoundLibrary {
+    pub fn new() -> Self {
+        Self {
+            packs: Mutex::new(scan()),
+            active: Mutex::new(None),
+        }
+    }
+
+    /// Paket dizinini yeniden tara ve paketleri döndür
+    pub fn reload(&self) -> Vec<SoundPack> {
+        let packs = scan();
+        match self.packs.lock() {
+            Ok(mut p) => *p = packs.clone(),
+            Err(e) => eprintln!("SoundLibrary::reload mutex poisoned: {}", e),
+        }
+        packs
+    }
+
+    /// Kullanılacak paketi seç (None: uygulamayla gelen sesler)
+    pub fn set_active(&self, pack: Option<String>) -> Result<(), String> {
+        let pack = pack.filter(|p| !p.is_empty());
+        if let Some(id) = &pack {
+            let packs = self.packs.lock().map_err(|_| "Lock failed")?;
+            if !packs.iter().any(|p| &p.id == id) {
+                return Err(format!("Sound pack not found: {}", id));
+            }
+        }
+        *self.active.lock().map_err(|_| "Lock failed")? = pack;
+        Ok(())
+    }
+
+    /// Seçili pakette olaya atanmış dosya
+    pub fn resolve(&self, event: SoundEvent) -> Option<PathBuf> {
+        let active = self.active.lock().ok()?.clone()?;
+        let packs = self.packs.lock().ok()?;
+        packs
+            .iter()
+            .find(|p| p.id == active)
+            .and_then(|p| p.files.get(&event).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test başına ayrı geçici dizin
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pomodoro-sounds-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Birkaç örnekli 16 bit mono PCM WAV
+    fn wav() -> Vec<u8> {
+        let samples = [0i16; 8];
+        let data_len = (samples.len() * 2) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+        bytes.extend_from_slice(&8000u32.to_le_bytes());
+        bytes.extend_from_slice(&16000u32.to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn resolves_files_inside_the_pack() {
+        let dir = temp_dir("inside");
+        std::fs::create_dir(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("sub/ding.wav"), wav()).unwrap();
+
+        let path = resolve_in(&dir, Path::new("sub/ding.wav")).unwrap();
+        assert_eq!(path, dir.join("sub/ding.wav").canonicalize().unwrap());
+        // Dizin içinde kalan ".." kabul edilir
+        assert!(resolve_in(&dir, Path::new("sub/../sub/ding.wav")).is_ok());
+        assert!(resolve_in(&dir, Path::new("missing.wav")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_pack() {
+        let root = temp_dir("outside");
+        let pack = root.join("pack");
+        std::fs::create_dir(&pack).unwrap();
+        std::fs::write(root.join("secret.wav"), wav()).unwrap();
+        std::os::unix::fs::symlink(root.join("secret.wav"), pack.join("link.wav")).unwrap();
+
+        assert!(resolve_in(&pack, Path::new("../secret.wav")).is_err());
+        assert!(resolve_in(&pack, &root.join("secret.wav")).is_err());
+        // Dışarıyı gösteren sembolik bağlantı da reddedilir
+        assert!(resolve_in(&pack, Path::new("link.wav")).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn validates_format_and_contents() {
+        let dir = temp_dir("validate");
+        std::fs::write(dir.join("ok.WAV"), wav()).unwrap();
+        std::fs::write(dir.join("broken.ogg"), b"not audio").unwrap();
+        std::fs::write(dir.join("notes.txt"), wav()).unwrap();
+
+        assert!(validate(&dir.join("ok.WAV")).is_ok());
+        assert!(validate(&dir.join("broken.ogg")).is_err());
+        assert_eq!(validate(&dir.join("notes.txt")), Err("unsupported format".to_string()));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn loads_valid_entries_and_reports_the_rest() {
+        let dir = temp_dir("load");
+        std::fs::write(dir.join("ding.wav"), wav()).unwrap();
+        std::fs::write(
+            dir.join(MANIFEST),
+            r#"{"name": "Retro", "sounds": {"workEnd": "ding.wav", "tick": "../ding.wav", "warning": "missing.wav"}}"#,
+        )
+        .unwrap();
+
+        let pack = load_pack(&dir).unwrap();
+        assert_eq!(pack.name, "Retro");
+        assert_eq!(pack.events, vec![SoundEvent::WorkEnd]);
+        assert_eq!(pack.errors.len(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
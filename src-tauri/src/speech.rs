@@ -0,0 +1,190 @@
+//! Oturum geçişlerinde sesli duyurular.
+//! Oturum bitiş sesinin çaldığı geçişlerde ("Çalışma oturumu bitti, 5 dakikalık mola")
+//! speech-dispatcher (`spd-say`) ile, o yoksa `espeak-ng` ile duyuru okunur.
+//! Pencere gizliyken hoparlörden takip edenler içindir.
+
+use crate::dbus::{SharedTimerState, TimerStatus};
+use crate::events::TimerEvent;
+use serde::Deserialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::process::Command;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Duration;
+
+/// Bitiş sesiyle üst üste binmemesi için bekleme
+const SPEAK_DELAY_MS: u64 = 1500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Tr,
+    En,
+}
+
+impl Language {
+    fn code(self) -> &'static str {
+        match self {
+            Language::Tr => "tr",
+            Language::En => "en",
+        }
+    }
+}
+
+/// Biten oturumdan sonraki mod için duyuru metni
+fn announcement(language: Language, from: &str, next: &TimerStatus) -> String {
+    let minutes = (next.duration / 60).max(1);
+    match (language, from, next.state.as_str()) {
+        (Language::Tr, "work", "longBreak") => {
+            format!("Çalışma oturumu bitti, {} dakikalık uzun mola", minutes)
+        }
+        (Language::Tr, "work", _) => format!("Çalışma oturumu bitti, {} dakikalık mola", minutes),
+        (Language::Tr, _, _) => format!("Mola bitti, {} dakika odaklanma zamanı", minutes),
+        (Language::En, "work", "longBreak") => {
+            format!("Work session finished, {} minute long break", minutes)
+        }
+        (Language::En, "work", _) => format!("Work session finished, {} minute break", minutes),
+        (Language::En, _, _) => format!("Break finished, {} minutes of focus", minutes),
+    }
+}
+
+/// Metni önce speech-dispatcher, o yoksa espeak-ng ile oku
+async fn speak(language: Language, text: &str) {
+    let result = Command::new("spd-say")
+        .args(["-l", language.code(), "--", text])
+        .kill_on_drop(true)
+        .status()
+        .await;
+    match result {
+        Ok(status) if status.success() => return,
+        Ok(status) => eprintln!("spd-say başarısız oldu ({}), espeak-ng deneniyor", status),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => eprintln!("spd-say çalıştırılamadı: {}", e),
+    }
+
+    let result = Command::new("espeak-ng")
+        .args(["-v", language.code(), "--", text])
+        .kill_on_drop(true)
+        .status()
+        .await;
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("espeak-ng başarısız oldu: {}", status),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("Sesli duyuru: spd-say veya espeak-ng bulunamadı")
+        }
+        Err(e) => eprintln!("espeak-ng çalıştırılamadı: {}", e),
+    }
+}
+
+pub struct Announcer {
+    enabled: Arc<AtomicBool>,
+    language: Arc<Mutex<Language>>,
+}
+
+impl Announcer {
+    pub fn new() -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(false)),
+            language: Arc::new(Mutex::new(Language::Tr)),
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    pub fn set_language(&self, language: Language) {
+        match self.language.lock() {
+            Ok(mut l) => *l = language,
+            Err(e) => eprintln!("Announcer::set_language mutex poisoned: {}", e),
+        }
+    }
+
+    /// Arka plan görevi olarak oturum bitişlerini dinle
+    pub fn start(&self, shared_state: Arc<SharedTimerState>) {
+        let enabled = self.enabled.clone();
+        let language = self.language.clone();
+        let mut events = shared_state.subscribe();
+
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let transition = match events.recv().await {
+                    Ok(t) => t,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+
+                // Yalnızca bitiş sesinin çaldığı geçişler (atlamalar sessizdir)
+                let TimerEvent::Finished { from, .. } = &transition.event else {
+                    continue;
+                };
+                if !enabled.load(Ordering::SeqCst) {
+                    continue;
+                }
+
+                let language = language.lock().map(|l| *l).unwrap_or(Language::Tr);
+                let text = announcement(language, from, &transition.status);
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(SPEAK_DELAY_MS)).await;
+                    speak(language, &text).await;
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(state: &str, duration: u32) -> TimerStatus {
+        TimerStatus {
+            state: state.to_string(),
+            time_left: duration,
+            is_active: true,
+            sessions_completed: 0,
+            duration,
+        }
+    }
+
+    #[test]
+    fn turkish_announcements() {
+        assert_eq!(
+            announcement(Language::Tr, "work", &next("shortBreak", 5 * 60)),
+            "Çalışma oturumu bitti, 5 dakikalık mola"
+        );
+        assert_eq!(
+            announcement(Language::Tr, "work", &next("longBreak", 15 * 60)),
+            "Çalışma oturumu bitti, 15 dakikalık uzun mola"
+        );
+        assert_eq!(
+            announcement(Language::Tr, "shortBreak", &next("work", 25 * 60)),
+            "Mola bitti, 25 dakika odaklanma zamanı"
+        );
+    }
+
+    #[test]
+    fn english_announcements() {
+        assert_eq!(
+            announcement(Language::En, "work", &next("shortBreak", 5 * 60)),
+            "Work session finished, 5 minute break"
+        );
+        assert_eq!(
+            announcement(Language::En, "work", &next("longBreak", 15 * 60)),
+            "Work session finished, 15 minute long break"
+        );
+        assert_eq!(
+            announcement(Language::En, "longBreak", &next("work", 25 * 60)),
+            "Break finished, 25 minutes of focus"
+        );
+    }
+
+    #[test]
+    fn short_sessions_announce_at_least_a_minute() {
+        assert_eq!(
+            announcement(Language::En, "work", &next("shortBreak", 30)),
+            "Work session finished, 1 minute break"
+        );
+    }
+}
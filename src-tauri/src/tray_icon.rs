@@ -0,0 +1,374 @@
+//! Çalışma anında çizilen tray ikonu.
+//! Geçen süre oranına göre dolan bir halka (renk moda göre), isteğe bağlı kalan
+//! dakika rakamları ve duraklatıldığında ortada duraklatma çubukları gösterir.
+//! İkon birkaç saniyede bir ve her timer olayında yeniden hesaplanır; aynı
+//! görünüm tekrar çizilmez, çizilen ikonlar önbellekte tutulur.
+
+use crate::dbus::{SharedTimerState, TimerStatus};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::image::Image;
+use tauri::AppHandle;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Notify;
+use tokio::time::Duration;
+
+const SIZE: u32 = 64;
+/// Kenar yumuşatma için piksel başına SUPERSAMPLE x SUPERSAMPLE örnek
+const SUPERSAMPLE: u32 = 4;
+const OUTER_RADIUS: f32 = 30.0;
+const INNER_RADIUS: f32 = 22.0;
+/// Halkanın kaç adımda dolduğu (her adım ayrı bir ikon)
+const PROGRESS_STEPS: u32 = 60;
+const REFRESH_SECS: u64 = 5;
+const CACHE_LIMIT: usize = 256;
+
+type Rgba = [u8; 4];
+
+const TRACK: Rgba = [128, 128, 128, 90];
+const FOREGROUND: Rgba = [255, 255, 255, 255];
+
+/// 3x5 rakam yazı tipi; her satırın en düşük 3 biti soldan sağa pikseller
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+const DIGIT_SCALE: f32 = 4.0;
+const DIGIT_GAP: f32 = 3.0;
+
+fn mode_color(state: &str) -> Rgba {
+    match state {
+        "shortBreak" => [34, 197, 94, 255],
+        "longBreak" => [59, 130, 246, 255],
+        _ => [239, 68, 68, 255],
+    }
+}
+
+/// Halkanın ortasında gösterilen içerik
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Center {
+    Empty,
+    Minutes(u32),
+    Paused,
+}
+
+/// Bir ikonun görünümünü belirleyen her şey (önbellek anahtarı)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct IconKey {
+    color: Rgba,
+    step: u32,
+    center: Center,
+}
+
+impl IconKey {
+    fn new(status: &TimerStatus, show_minutes: bool) -> Self {
+        let duration = status.duration.max(1);
+        let elapsed = duration.saturating_sub(status.time_left);
+        let step = elapsed * PROGRESS_STEPS / duration;
+        let paused = !status.is_active && status.time_left < status.duration;
+
+        let mut color = mode_color(&status.state);
+        if paused {
+            color[3] = 140;
+        }
+
+        let center = if paused {
+            Center::Paused
+        } else if show_minutes {
+            Center::Minutes(status.time_left.div_ceil(60).min(99))
+        } else {
+            Center::Empty
+        };
+
+        Self {
+            color,
+            step,
+            center,
+        }
+    }
+}
+
+/// (px, py) noktası ortadaki içeriğin üzerinde mi
+fn center_hit(center: Center, px: f32, py: f32) -> bool {
+    let mid = SIZE as f32 / 2.0;
+    match center {
+        Center::Empty => false,
+        Center::Paused => {
+            let in_bar = |left: f32| px >= left && px < left + 5.0;
+            (py >= mid - 9.0 && py < mid + 9.0) && (in_bar(mid - 8.0) || in_bar(mid + 3.0))
+        }
+        Center::Minutes(minutes) => {
+            let digits: Vec<usize> = if minutes >= 10 {
+                vec![(minutes / 10) as usize, (minutes % 10) as usize]
+            } else {
+                vec![minutes as usize]
+            };
+            let digit_w = 3.0 * DIGIT_SCALE;
+            let width = digits.len() as f32 * digit_w + (digits.len() - 1) as f32 * DIGIT_GAP;
+            let left = mid - width / 2.0;
+            let top = mid - 2.5 * DIGIT_SCALE;
+
+            let row = ((py - top) / DIGIT_SCALE).floor();
+            if !(0.0..5.0).contains(&row) || px < left {
+                return false;
+            }
+            let offset = px - left;
+            let index = (offset / (digit_w + DIGIT_GAP)) as usize;
+            let within = offset - index as f32 * (digit_w + DIGIT_GAP);
+            let Some(&digit) = digits.get(index) else {
+                return false;
+            };
+            if within >= digit_w {
+                return false;
+            }
+            let col = (within / DIGIT_SCALE) as u32;
+            DIGITS[digit][row as usize] & (0b100 >> col) != 0
+        }
+    }
+}
+
+/// İkonu RGBA piksel dizisi olarak çiz
+fn render(key: &IconKey) -> Vec<u8> {
+    let mut rgba = vec![0u8; (SIZE * SIZE * 4) as usize];
+    let mid = SIZE as f32 / 2.0;
+    let progress = key.step as f32 / PROGRESS_STEPS as f32;
+    let samples = (SUPERSAMPLE * SUPERSAMPLE) as f32;
+
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (mut fill, mut track, mut glyph) = (0u32, 0u32, 0u32);
+            for sy in 0..SUPERSAMPLE {
+                for sx in 0..SUPERSAMPLE {
+                    let px = x as f32 + (sx as f32 + 0.5) / SUPERSAMPLE as f32;
+                    let py = y as f32 + (sy as f32 + 0.5) / SUPERSAMPLE as f32;
+                    let (dx, dy) = (px - mid, py - mid);
+                    let radius = dx.hypot(dy);
+
+                    if (INNER_RADIUS..=OUTER_RADIUS).contains(&radius) {
+                        // Saat 12'den başlayıp saat yönünde 0..1
+                        let angle = (dx.atan2(-dy) / std::f32::consts::TAU).rem_euclid(1.0);
+                        if angle < progress {
+                            fill += 1;
+                        } else {
+                            track += 1;
+                        }
+                    } else if center_hit(key.center, px, py) {
+                        glyph += 1;
+                    }
+                }
+            }
+
+            // Bölgeler çakışmadığı için örnek sayılarına göre ağırlıklı ortalama yeterli
+            let parts = [(fill, key.color), (track, TRACK), (glyph, FOREGROUND)];
+            let weight: f32 = parts.iter().map(|(n, c)| *n as f32 * c[3] as f32).sum();
+            if weight == 0.0 {
+                continue;
+            }
+
+            let i = ((y * SIZE + x) * 4) as usize;
+            for channel in 0..3 {
+                let sum: f32 = parts
+                    .iter()
+                    .map(|(n, c)| *n as f32 * c[3] as f32 * c[channel] as f32)
+                    .sum();
+                rgba[i + channel] = (sum / weight).round() as u8;
+            }
+            rgba[i + 3] = (weight / samples).round() as u8;
+        }
+    }
+    rgba
+}
+
+/// Moda göre hazır PNG ikonunu kullan (dinamik ikon kapalıyken)
+pub fn set_static_icon(app: &AppHandle, mode: &str) -> Result<(), String> {
+    let tray = app.tray_by_id("main_tray").ok_or("Tray not found")?;
+
+    let icon_file = match mode {
+        "work" => "work.png",
+        "shortBreak" => "short-break.png",
+        "longBreak" => "long-break.png",
+        _ => "work.png",
+    };
+
+    if let Some(icon_path) = crate::resolve_path(app, icon_file, "icons") {
+        match Image::from_path(&icon_path) {
+            Ok(icon) => {
+                let _ = tray.set_icon(Some(icon));
+            }
+            Err(_) => {
+                if let Some(icon) = app.default_window_icon() {
+                    let _ = tray.set_icon(Some(icon.clone()));
+                }
+            }
+        }
+    } else if let Some(icon) = app.default_window_icon() {
+        let _ = tray.set_icon(Some(icon.clone()));
+    }
+
+    Ok(())
+}
+
+pub struct TrayIconRenderer {
+    dynamic: Arc<AtomicBool>,
+    show_minutes: Arc<AtomicBool>,
+    refresh: Arc<Notify>,
+}
+
+impl TrayIconRenderer {
+    pub fn new() -> Self {
+        Self {
+            dynamic: Arc::new(AtomicBool::new(true)),
+            show_minutes: Arc::new(AtomicBool::new(false)),
+            refresh: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        self.dynamic.load(Ordering::SeqCst)
+    }
+
+    /// İkon stilini değiştir ve hemen yeniden çiz
+    pub fn set_style(&self, dynamic: bool, show_minutes: bool) {
+        self.dynamic.store(dynamic, Ordering::SeqCst);
+        self.show_minutes.store(show_minutes, Ordering::SeqCst);
+        self.refresh.notify_one();
+    }
+
+    /// Arka plan görevi olarak ikonu güncel tut
+    pub fn start(&self, app_handle: AppHandle, shared_state: Arc<SharedTimerState>) {
+        let dynamic = self.dynamic.clone();
+        let show_minutes = self.show_minutes.clone();
+        let refresh = self.refresh.clone();
+        let mut events = shared_state.subscribe();
+
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(REFRESH_SECS));
+            let mut cache: HashMap<IconKey, Image<'static>> = HashMap::new();
+            // Son gösterilen dinamik ikon (None: henüz çizilmedi veya statik ikon gösteriliyor)
+            let mut shown: Option<IconKey> = None;
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = refresh.notified() => {}
+                    received = events.recv() => if let Err(RecvError::Closed) = received {
+                        break;
+                    },
+                }
+
+                let status = shared_state.snapshot();
+                if !dynamic.load(Ordering::SeqCst) {
+                    if shown.take().is_some() {
+                        if let Err(e) = set_static_icon(&app_handle, &status.state) {
+                            eprintln!("Tray ikonu güncellenemedi: {}", e);
+                        }
+                    }
+                    continue;
+                }
+
+                let key = IconKey::new(&status, show_minutes.load(Ordering::SeqCst));
+                if shown == Some(key) {
+                    continue;
+                }
+                let Some(tray) = app_handle.tray_by_id("main_tray") else {
+                    continue;
+                };
+
+                if cache.len() >= CACHE_LIMIT {
+                    cache.clear();
+                }
+                let icon = cache
+                    .entry(key)
+                    .or_insert_with(|| Image::new_owned(render(&key), SIZE, SIZE))
+                    .clone();
+                match tray.set_icon(Some(icon)) {
+                    Ok(()) => shown = Some(key),
+                    Err(e) => eprintln!("Tray ikonu güncellenemedi: {}", e),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DURATION: u32 = 25 * 60;
+
+    fn status(time_left: u32, is_active: bool) -> TimerStatus {
+        TimerStatus {
+            state: "work".to_string(),
+            time_left,
+            is_active,
+            sessions_completed: 0,
+            duration: DURATION,
+        }
+    }
+
+    fn pixel(rgba: &[u8], x: u32, y: u32) -> Rgba {
+        let i = ((y * SIZE + x) * 4) as usize;
+        rgba[i..i + 4].try_into().unwrap()
+    }
+
+    /// Geçen süre oranına göre çizilmiş ikon
+    fn rendered(elapsed: u32) -> Vec<u8> {
+        render(&IconKey::new(&status(DURATION - elapsed, true), false))
+    }
+
+    #[test]
+    fn key_changes_only_with_the_appearance() {
+        // Aynı adım ve aynı dakika içindeki saniyeler aynı ikonu kullanır
+        assert_eq!(IconKey::new(&status(1000, true), true), IconKey::new(&status(990, true), true));
+        assert_ne!(IconKey::new(&status(1000, true), true), IconKey::new(&status(900, true), true));
+
+        let key = IconKey::new(&status(DURATION / 2, true), true);
+        assert_eq!(key.step, PROGRESS_STEPS / 2);
+        assert_eq!(key.center, Center::Minutes(13));
+        assert_eq!(key.color, mode_color("work"));
+        assert_eq!(IconKey::new(&status(0, true), false).step, PROGRESS_STEPS);
+        assert_eq!(IconKey::new(&status(DURATION / 2, true), false).center, Center::Empty);
+    }
+
+    #[test]
+    fn key_shows_pause_only_after_start() {
+        let paused = IconKey::new(&status(DURATION / 2, false), true);
+        assert_eq!(paused.center, Center::Paused);
+        assert_eq!(paused.color[3], 140);
+
+        let idle = IconKey::new(&status(DURATION, false), true);
+        assert_eq!(idle.center, Center::Minutes(25));
+        assert_eq!(idle.color[3], 255);
+    }
+
+    #[test]
+    fn render_fills_the_ring_with_progress() {
+        let color = mode_color("work");
+        // Halka üzerinde saat 12, 3 ve 9 yönündeki pikseller
+        let (top, right, left) = ((32, 6), (57, 31), (6, 32));
+
+        for (elapsed, expected) in [
+            (0, [TRACK, TRACK, TRACK]),
+            (DURATION / 2, [color, color, TRACK]),
+            (DURATION, [color, color, color]),
+        ] {
+            let rgba = rendered(elapsed);
+            assert_eq!(rgba.len(), (SIZE * SIZE * 4) as usize);
+            for ((x, y), expected) in [top, right, left].into_iter().zip(expected) {
+                assert_eq!(pixel(&rgba, x, y), expected, "elapsed {} at ({}, {})", elapsed, x, y);
+            }
+            // Halkanın dışı ve boş orta saydam
+            assert_eq!(pixel(&rgba, 0, 0)[3], 0);
+            assert_eq!(pixel(&rgba, 32, 32)[3], 0);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Timer durumuna göre güncellenen tray menüsü.
+//! Başlıkta mod ve kalan süre, doğru etiketli Başlat/Duraklat, uzatma öğeleri,
+//! mola atlama ve bugünkü pomodoro sayısı gösterilir. Öğeler bir kez oluşturulur;
+//! her durum değişikliğinde yalnızca değişen etiketler güncellenir.
+
+use crate::dbus::TimerStatus;
+use crate::mpris::mode_title;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::{AppHandle, Wry};
+
+/// Menüde gösterilen, durumdan türetilen her şey
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Labels {
+    header: String,
+    start_pause: &'static str,
+    started: bool,
+    skip: &'static str,
+    today: String,
+}
+
+impl Labels {
+    fn new(status: &TimerStatus, today: Option<i64>) -> Self {
+        let started = status.is_active || status.time_left < status.duration;
+        let paused = started && !status.is_active;
+
+        let mut header = format!(
+            "{} — {:02}:{:02}",
+            mode_title(&status.state),
+            status.time_left / 60,
+            status.time_left % 60
+        );
+        if paused {
+            header.push_str(" (duraklatıldı)");
+        }
+
+        let start_pause = if status.is_active {
+            "Duraklat"
+        } else if paused {
+            "Devam Et"
+        } else {
+            "Başlat"
+        };
+        let skip = if status.state == "work" {
+            "Atla"
+        } else {
+            "Molayı Atla"
+        };
+
+        Self {
+            header,
+            start_pause,
+            started,
+            skip,
+            today: format!("Bugün: {} pomodoro", today.unwrap_or(0)),
+        }
+    }
+}
+
+pub struct TrayMenu {
+    menu: Menu<Wry>,
+    header: MenuItem<Wry>,
+    start_pause: MenuItem<Wry>,
+    extend_short: MenuItem<Wry>,
+    extend_long: MenuItem<Wry>,
+    skip: MenuItem<Wry>,
+    today: MenuItem<Wry>,
+    shown: Option<Labels>,
+}
+
+impl TrayMenu {
+    /// Menü öğelerini varsayılan durumla oluştur
+    pub fn new(app: &AppHandle) -> tauri::Result<Self> {
+        let labels = Labels::new(&TimerStatus::default(), None);
+
+        let header = MenuItem::with_id(app, "header", &labels.header, false, None::<&str>)?;
+        let toggle = MenuItem::with_id(app, "toggle", "Göster/Gizle", true, None::<&str>)?;
+        let start_pause =
+            MenuItem::with_id(app, "start_pause", labels.start_pause, true, None::<&str>)?;
+        let extend_short = MenuItem::with_id(app, "extend_1", "+1 dk", false, None::<&str>)?;
+        let extend_long = MenuItem::with_id(app, "extend_5", "+5 dk", false, None::<&str>)?;
+        let skip = MenuItem::with_id(app, "skip", labels.skip, true, None::<&str>)?;
+        let reset = MenuItem::with_id(app, "reset", "Sıfırla", true, None::<&str>)?;
+        let today = MenuItem::with_id(app, "today", &labels.today, false, None::<&str>)?;
+        let quit = MenuItem::with_id(app, "quit", "Çıkış", true, None::<&str>)?;
+
+        let menu = Menu::with_items(
+            app,
+            &[
+                &header,
+                &PredefinedMenuItem::separator(app)?,
+                &start_pause,
+                &extend_short,
+                &extend_long,
+                &skip,
+                &reset,
+                &PredefinedMenuItem::separator(app)?,
+                &today,
+                &PredefinedMenuItem::separator(app)?,
+                &toggle,
+                &quit,
+            ],
+        )?;
+
+        Ok(Self {
+            menu,
+            header,
+            start_pause,
+            extend_short,
+            extend_long,
+            skip,
+            today,
+            shown: Some(labels),
+        })
+    }
+
+    pub fn menu(&self) -> &Menu<Wry> {
+        &self.menu
+    }
+
+    /// Etiketleri güncel duruma getir (yalnızca değişenler yeniden yazılır)
+    pub fn update(&mut self, status: &TimerStatus, today: Option<i64>) {
+        let labels = Labels::new(status, today);
+        let old = self.shown.take();
+
+        let mut result = Ok(());
+        if old.as_ref().map(|l| &l.header) != Some(&labels.header) {
+            result = result.and(self.header.set_text(&labels.header));
+        }
+        if old.as_ref().map(|l| l.start_pause) != Some(labels.start_pause) {
+            result = result.and(self.start_pause.set_text(labels.start_pause));
+        }
+        if old.as_ref().map(|l| l.started) != Some(labels.started) {
+            result = result
+                .and(self.extend_short.set_enabled(labels.started))
+                .and(self.extend_long.set_enabled(labels.started));
+        }
+        if old.as_ref().map(|l| l.skip) != Some(labels.skip) {
+            result = result.and(self.skip.set_text(labels.skip));
+        }
+        if old.as_ref().map(|l| &l.today) != Some(&labels.today) {
+            result = result.and(self.today.set_text(&labels.today));
+        }
+
+        match result {
+            Ok(()) => self.shown = Some(labels),
+            Err(e) => eprintln!("Tray menüsü güncellenemedi: {}", e),
+        }
+    }
+}
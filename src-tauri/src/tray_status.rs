@@ -0,0 +1,122 @@
+//! Tray ipucu (tooltip), başlığı ve menüsünün canlı güncellenmesi.
+//! Mod, kalan süre ve bugünkü tamamlanan pomodoro sayısı doğrudan
+//! SharedTimerState ve veritabanından okunur; webview'in bir şey çağırması
+//! gerekmez. Başlık, gösteren platformlarda (ör. appindicator etiketi) yalnızca
+//! kalan süredir. Menü etiketleri için bkz. tray_menu.
+
+use crate::db::Database;
+use crate::dbus::{SharedTimerState, TimerStatus};
+use crate::mpris::mode_title;
+use crate::tray_menu::TrayMenu;
+use chrono::Utc;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Notify;
+use tokio::time::Duration;
+
+fn format_time(seconds: u32) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn title_text(status: &TimerStatus) -> String {
+    format_time(status.time_left)
+}
+
+fn tooltip_text(status: &TimerStatus, today: Option<i64>) -> String {
+    let paused = !status.is_active && status.time_left < status.duration;
+    let mut text = format!("{} — {}", mode_title(&status.state), format_time(status.time_left));
+    if paused {
+        text.push_str(" (duraklatıldı)");
+    }
+    if let Some(count) = today {
+        text.push_str(&format!("\nBugün: {} pomodoro", count));
+    }
+    text
+}
+
+/// Bugün tamamlanan çalışma oturumu sayısı (oturumlar UTC tarihle kaydedilir)
+fn today_count(app: &AppHandle, date: &str) -> Option<i64> {
+    let db = app.try_state::<Database>()?;
+    match db.get_daily_stats(date) {
+        Ok(stat) => Some(stat.count),
+        Err(e) => {
+            eprintln!("Bugünkü oturum sayısı okunamadı: {}", e);
+            None
+        }
+    }
+}
+
+pub struct TrayStatus {
+    refresh: Arc<Notify>,
+}
+
+impl TrayStatus {
+    pub fn new() -> Self {
+        Self {
+            refresh: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Bugünkü sayıyı yeniden oku (yeni oturum kaydedildiğinde)
+    pub fn refresh(&self) {
+        self.refresh.notify_one();
+    }
+
+    /// Arka plan görevi olarak ipucu, başlık ve menüyü güncel tut
+    pub fn start(
+        &self,
+        app_handle: AppHandle,
+        shared_state: Arc<SharedTimerState>,
+        mut menu: TrayMenu,
+    ) {
+        let refresh = self.refresh.clone();
+        let mut events = shared_state.subscribe();
+
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            let mut date = String::new();
+            let mut today: Option<i64> = None;
+            let mut shown: Option<(String, String)> = None;
+            let mut stale = true;
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = refresh.notified() => stale = true,
+                    received = events.recv() => if let Err(RecvError::Closed) = received {
+                        break;
+                    },
+                }
+
+                // Gün değiştiğinde sayı sıfırlanır
+                let current_date = Utc::now().format("%Y-%m-%d").to_string();
+                if stale || current_date != date {
+                    today = today_count(&app_handle, &current_date);
+                    date = current_date;
+                    stale = false;
+                }
+
+                let status = shared_state.snapshot();
+                menu.update(&status, today);
+
+                let texts = (tooltip_text(&status, today), title_text(&status));
+                if shown.as_ref() == Some(&texts) {
+                    continue;
+                }
+                let Some(tray) = app_handle.tray_by_id("main_tray") else {
+                    continue;
+                };
+
+                let (tooltip, title) = &texts;
+                if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+                    eprintln!("Tray ipucu güncellenemedi: {}", e);
+                }
+                if let Err(e) = tray.set_title(Some(title)) {
+                    eprintln!("Tray başlığı güncellenemedi: {}", e);
+                }
+                shown = Some(texts);
+            }
+        });
+    }
+}
@@ -0,0 +1,192 @@
+//! Ana pencerenin konumu, boyutu ve görünürlüğünün yeniden başlatmalar arasında
+//! hatırlanması. Durum uygulama veri dizinindeki window-state.json dosyasında
+//! tutulur: geometri pencere olaylarından izlenir, dosya pencere kapatılıp
+//! tray'e gizlendiğinde ve uygulamadan çıkarken yazılır. Son görünürlük yalnızca
+//! `--restore` ile (otomatik başlatma, oturum geri yükleme) uygulanır; elle
+//! başlatılan uygulama pencereyi açar. "Tray'de başla" ayarı da aynı dosyadadır;
+//! böylece arayüz yüklenmeden önce okunabilir.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{PhysicalPosition, PhysicalSize, WebviewWindow, Window, WindowEvent};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct WindowState {
+    /// Fiziksel piksel; Wayland'de konum okunamadığından boş kalır
+    position: Option<(i32, i32)>,
+    size: Option<(u32, u32)>,
+    maximized: bool,
+    /// Son çıkışta pencere gizli miydi (ilk çalıştırmada görünür; bkz. start_hidden)
+    hidden: bool,
+    /// Her zaman yalnızca tray'de başla
+    start_minimized: bool,
+}
+
+pub struct WindowStateStore {
+    path: PathBuf,
+    state: Mutex<WindowState>,
+}
+
+impl WindowStateStore {
+    pub fn new(app_data_dir: PathBuf) -> Self {
+        let path = app_data_dir.join("window-state.json");
+        let state = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                eprintln!("Pencere durumu okunamadı ({}): {}", path.display(), e);
+                WindowState::default()
+            }),
+            Err(_) => WindowState::default(),
+        };
+        Self {
+            path,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Ayar açıksa tray'de başlanır. `restore` (otomatik başlatma, oturum geri
+    /// yükleme) verildiyse son çıkıştaki görünürlük de korunur.
+    pub fn start_hidden(&self, restore: bool) -> bool {
+        match self.state.lock() {
+            Ok(state) => state.start_minimized || (restore && state.hidden),
+            Err(e) => {
+                eprintln!("WindowStateStore::start_hidden mutex poisoned: {}", e);
+                false
+            }
+        }
+    }
+
+    pub fn set_start_minimized(&self, enabled: bool) {
+        match self.state.lock() {
+            Ok(mut state) => {
+                if state.start_minimized != enabled {
+                    state.start_minimized = enabled;
+                    self.write(&state);
+                }
+            }
+            Err(e) => eprintln!("WindowStateStore::set_start_minimized mutex poisoned: {}", e),
+        }
+    }
+
+    /// Kaydedilen boyutu ve (hâlâ bir ekranın içindeyse) konumu uygula
+    pub fn restore(&self, window: &WebviewWindow) {
+        let state = match self.state.lock() {
+            Ok(state) => state.clone(),
+            Err(e) => {
+                eprintln!("WindowStateStore::restore mutex poisoned: {}", e);
+                return;
+            }
+        };
+
+        if let Some((width, height)) = state.size {
+            if let Err(e) = window.set_size(PhysicalSize::new(width, height)) {
+                eprintln!("Pencere boyutu geri yüklenemedi: {}", e);
+            }
+        }
+        if let Some((x, y)) = state.position {
+            // Ekran çıkarıldıysa pencere görünmez bir konuma yerleştirilmesin
+            let on_screen = window.available_monitors().is_ok_and(|monitors| {
+                monitors.iter().any(|m| {
+                    let (pos, size) = (m.position(), m.size());
+                    x >= pos.x
+                        && y >= pos.y
+                        && x < pos.x + size.width as i32
+                        && y < pos.y + size.height as i32
+                })
+            });
+            if on_screen {
+                if let Err(e) = window.set_position(PhysicalPosition::new(x, y)) {
+                    eprintln!("Pencere konumu geri yüklenemedi: {}", e);
+                }
+            }
+        }
+        if state.maximized {
+            let _ = window.maximize();
+        }
+    }
+
+    /// Taşıma/boyutlandırma olaylarından geometriyi izle
+    pub fn track(&self, window: &Window, event: &WindowEvent) {
+        if !matches!(event, WindowEvent::Moved(_) | WindowEvent::Resized(_)) {
+            return;
+        }
+        // Simge durumundaki veya ekranı kaplayan pencerenin geometrisi saklanmaz
+        let maximized = window.is_maximized().unwrap_or(false);
+        if window.is_minimized().unwrap_or(false) {
+            return;
+        }
+
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(e) => {
+                eprintln!("WindowStateStore::track mutex poisoned: {}", e);
+                return;
+            }
+        };
+        state.maximized = maximized;
+        if maximized {
+            return;
+        }
+        match event {
+            WindowEvent::Moved(pos) => state.position = Some((pos.x, pos.y)),
+            WindowEvent::Resized(size) if size.width > 0 && size.height > 0 => {
+                state.size = Some((size.width, size.height))
+            }
+            _ => {}
+        }
+    }
+
+    /// Görünürlüğü kaydet ve dosyaya yaz
+    pub fn save(&self, visible: bool) {
+        match self.state.lock() {
+            Ok(mut state) => {
+                state.hidden = !visible;
+                self.write(&state);
+            }
+            Err(e) => eprintln!("WindowStateStore::save mutex poisoned: {}", e),
+        }
+    }
+
+    fn write(&self, state: &WindowState) {
+        let result = serde_json::to_string_pretty(state)
+            .map_err(std::io::Error::other)
+            .and_then(|text| std::fs::write(&self.path, text));
+        if let Err(e) = result {
+            eprintln!(
+                "Pencere durumu kaydedilemedi ({}): {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(hidden: bool, start_minimized: bool) -> WindowStateStore {
+        WindowStateStore {
+            path: PathBuf::new(),
+            state: Mutex::new(WindowState {
+                hidden,
+                start_minimized,
+                ..WindowState::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn saved_visibility_applies_only_when_restoring() {
+        assert!(!store(true, false).start_hidden(false));
+        assert!(store(true, false).start_hidden(true));
+        assert!(!store(false, false).start_hidden(true));
+    }
+
+    #[test]
+    fn start_minimized_always_hides() {
+        assert!(store(false, true).start_hidden(false));
+        assert!(store(false, true).start_hidden(true));
+    }
+}
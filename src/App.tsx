@@ -2,19 +2,45 @@ import { StatsView } from "@/components/StatsView";
 import { StrictBreakOverlay } from "@/components/StrictBreakOverlay";
 import { Timer } from "@/components/Timer";
 import { Button } from "@/components/ui/button";
-import { useTimerStore } from "@/store/useTimerStore";
+import {
+  type ShortcutConflict,
+  type SoundChannel,
+  useTimerStore,
+} from "@/store/useTimerStore";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { BarChart3, Clock } from "lucide-react";
+import { BarChart3, Clock, Keyboard, VolumeX } from "lucide-react";
 import { useEffect, useState } from "react";
 import "./index.css";
 
 type Tab = "timer" | "stats";
 
+/** Rust tarafındaki TimerCommand (bkz. control.rs) */
+type TimerCommand =
+  | { command: "toggle" | "start" | "stop" | "skip" | "reset" | "startBreak" }
+  | { command: "extend"; seconds: number };
+
+/** Rust tarafındaki AudioStatus (bkz. audio.rs) */
+interface AudioStatus {
+  available: boolean;
+  device: string | null;
+  error: string | null;
+}
+
 function App() {
-  const { mode, settings, isActive, timeLeft, sessionsCompleted } =
-    useTimerStore();
+  const {
+    mode,
+    settings,
+    isActive,
+    timeLeft,
+    sessionsCompleted,
+    transitionReason,
+  } = useTimerStore();
   const [activeTab, setActiveTab] = useState<Tab>("timer");
+  const [audioError, setAudioError] = useState<string | null>(null);
+  const [shortcutConflicts, setShortcutConflicts] = useState<
+    ShortcutConflict[]
+  >([]);
 
   // Strict Break Fullscreen
   useEffect(() => {
@@ -23,10 +49,18 @@ function App() {
     invoke("set_fullscreen", { fullscreen }).catch(() => {});
   }, [mode, settings.enableStrictBreak, isActive]);
 
-  // Tray icon'u moda göre güncelle
+  // Tray ikon stilini Rust'a senkronize et
+  useEffect(() => {
+    invoke("set_tray_icon_style", {
+      dynamic: settings.dynamicTrayIcon,
+      showMinutes: settings.trayIconMinutes,
+    }).catch(() => {});
+  }, [settings.dynamicTrayIcon, settings.trayIconMinutes]);
+
+  // Tray icon'u moda göre güncelle (dinamik ikon kapalıyken)
   useEffect(() => {
     invoke("update_tray_icon", { mode }).catch(() => {});
-  }, [mode]);
+  }, [mode, settings.dynamicTrayIcon]);
 
   // D-Bus shared state'i güncelle
   useEffect(() => {
@@ -35,8 +69,18 @@ function App() {
       timeLeft,
       isActive,
       sessionsCompleted,
+      duration: settings[mode] * 60,
+      // Yalnızca mod değiştiğinde okunur: bitti mi, atlandı mı
+      reason: transitionReason,
     }).catch(() => {});
-  }, [mode, timeLeft, isActive, sessionsCompleted]);
+  }, [
+    mode,
+    timeLeft,
+    isActive,
+    sessionsCompleted,
+    settings,
+    transitionReason,
+  ]);
 
   // Idle detection ayarını Rust'a senkronize et
   useEffect(() => {
@@ -45,34 +89,157 @@ function App() {
     );
   }, [settings.pauseWhenIdle]);
 
+  // Uyku/ekran koruyucu engelleme ayarını Rust'a senkronize et
+  useEffect(() => {
+    invoke("set_sleep_inhibit", { enabled: settings.inhibitSleep }).catch(
+      () => {},
+    );
+  }, [settings.inhibitSleep]);
+
+  // Tray'de başlama ayarını Rust'a senkronize et (açılışta arayüzden önce okunur)
+  useEffect(() => {
+    invoke("set_start_minimized", { enabled: settings.startMinimized }).catch(
+      () => {},
+    );
+  }, [settings.startMinimized]);
+
+  // Oturum bitişi öncesi uyarı süresini Rust'a senkronize et
+  useEffect(() => {
+    invoke("set_notification_warning", {
+      seconds: settings.warningSeconds,
+    }).catch(() => {});
+  }, [settings.warningSeconds]);
+
+  // Bitiş öncesi uyarı çanı ve geri sayım tıklarını Rust'a senkronize et
+  useEffect(() => {
+    invoke("set_sound_cues", {
+      warningSeconds: settings.warningSound ? settings.warningSeconds : 0,
+      countdownSeconds: settings.countdownSeconds,
+    }).catch(() => {});
+  }, [settings.warningSound, settings.warningSeconds, settings.countdownSeconds]);
+
+  // Sesli duyuru ayarlarını Rust'a senkronize et
+  useEffect(() => {
+    invoke("set_announcements", {
+      enabled: settings.announceTransitions,
+      language: settings.language,
+    }).catch(() => {});
+  }, [settings.announceTransitions, settings.language]);
+
+  // Medya oynatıcı politikasını Rust'a senkronize et
+  useEffect(() => {
+    invoke("set_media_policy", { policy: settings.mediaPolicy }).catch(
+      () => {},
+    );
+  }, [settings.mediaPolicy]);
+
+  // Kanal ses düzeylerini Rust mikserine senkronize et
+  useEffect(() => {
+    for (const [channel, volume] of Object.entries(settings.volumes)) {
+      invoke("set_volume", { channel, volume: volume / 100 }).catch(() => {});
+    }
+  }, [settings.volumes]);
+
+  // Metronom temposunu Rust'a senkronize et (çalan metronom hemen uyar)
+  useEffect(() => {
+    invoke("set_metronome_bpm", { bpm: settings.metronomeBpm }).catch(
+      () => {},
+    );
+  }, [settings.metronomeBpm]);
+
+  // Seçili ses paketini Rust'a senkronize et
+  useEffect(() => {
+    invoke("set_sound_pack", { pack: settings.soundPack || null }).catch(
+      () => {},
+    );
+  }, [settings.soundPack]);
+
+  // Global kısayol tablosunu Rust'a senkronize et; kaydedilemeyenler uyarı olarak gösterilir
+  useEffect(() => {
+    invoke<ShortcutConflict[]>("set_shortcuts", {
+      shortcuts: settings.shortcuts,
+    })
+      .then(setShortcutConflicts)
+      .catch(() => {});
+  }, [settings.shortcuts]);
+
+  // Portal kısayolları bağlandıktan sonra reddedilenler ayrıca bildirilir
+  useEffect(() => {
+    const unlisten = listen<ShortcutConflict[]>("shortcut-conflicts", (event) =>
+      setShortcutConflicts(event.payload),
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // Ses çıkışı durumu: cihaz yoksa veya açılamadıysa uyarı göster
+  useEffect(() => {
+    invoke<AudioStatus>("get_audio_status")
+      .then((status) => setAudioError(status.error))
+      .catch(() => {});
+    const unlisten = listen<AudioStatus>("audio-status", (event) =>
+      setAudioError(event.payload.error),
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
   // Event listener'ları
   useEffect(() => {
     const store = useTimerStore.getState;
     const eventUnlisteners = [
-      // Tray event'leri
-      listen("reset-timer", () => store().reset()),
-      listen("tray-start-stop", () => store().toggle()),
-      listen("tray-pause-resume", () => store().toggle()),
-      listen("tray-skip", () => store().skip()),
-      // D-Bus event'leri
-      listen("dbus-toggle", () => store().toggle()),
-      listen("dbus-start", () => {
-        if (!store().isActive) store().toggle();
-      }),
-      listen("dbus-stop", () => {
-        if (store().isActive) store().toggle();
-      }),
-      listen("dbus-skip", () => store().skip()),
-      listen("dbus-reset", () => store().reset()),
-      listen<number>("dbus-extend", (event) => store().extend(event.payload)),
-      // Idle detection event'leri
-      listen("idle-pause", () => {
-        if (store().isActive) store().toggle();
-      }),
-      listen("idle-resume", () => {
-        if (!store().isActive) store().toggle();
+      // Tray, kısayol, D-Bus, soket, HTTP, bildirim ve idle komutları (bkz. control.rs)
+      listen<TimerCommand>("timer-command", (event) => {
+        const command = event.payload;
+        switch (command.command) {
+          case "toggle":
+            store().toggle();
+            break;
+          case "start":
+            if (!store().isActive) store().toggle();
+            break;
+          case "stop":
+            if (store().isActive) store().toggle();
+            break;
+          case "skip":
+            store().skip();
+            break;
+          case "reset":
+            store().reset();
+            break;
+          case "extend":
+            store().extend(command.seconds);
+            break;
+          case "startBreak":
+            if (store().mode === "work") store().setMode("shortBreak");
+            if (!store().isActive) store().toggle();
+            break;
+        }
       }),
+      // CLI / D-Bus üzerinden değişen ses düzeyi (timer sıfırlanmadan kaydedilir)
+      listen<{ channel: SoundChannel; volume: number }>(
+        "volume-changed",
+        (event) => {
+          const { channel, volume } = event.payload;
+          const { settings } = store();
+          useTimerStore.setState({
+            settings: {
+              ...settings,
+              volumes: {
+                ...settings.volumes,
+                [channel]: Math.round(volume * 100),
+              },
+            },
+          });
+        },
+      ),
     ];
+    // Dinleyiciler kurulunca Rust tarafında bekleyen komutları iste
+    Promise.all(eventUnlisteners)
+      .then(() => invoke("frontend_ready"))
+      .catch(() => {});
 
     return () => {
       for (const p of eventUnlisteners) {
@@ -86,6 +253,29 @@ function App() {
       <StrictBreakOverlay />
 
       <main className="flex-1 relative overflow-hidden flex flex-col">
+        {audioError && (
+          <div
+            className="flex items-center justify-center gap-2 px-4 py-1.5 text-xs text-muted-foreground bg-white/5 border-b border-white/10"
+            title={audioError}
+          >
+            <VolumeX className="w-3.5 h-3.5" />
+            <span>Ses çıkışı bulunamadı, sesler çalınmayacak</span>
+          </div>
+        )}
+        {shortcutConflicts.length > 0 && (
+          <div
+            className="flex items-center justify-center gap-2 px-4 py-1.5 text-xs text-muted-foreground bg-white/5 border-b border-white/10"
+            title={shortcutConflicts
+              .map((c) => `${c.accelerator}: ${c.message}`)
+              .join("\n")}
+          >
+            <Keyboard className="w-3.5 h-3.5" />
+            <span>
+              {shortcutConflicts.length} kısayol kaydedilemedi, ayarlardan
+              değiştirin
+            </span>
+          </div>
+        )}
         <div className="flex-1 overflow-hidden">
           {activeTab === "timer" ? (
             <div className="min-h-full flex flex-col items-center justify-center animate-fade-in-up">
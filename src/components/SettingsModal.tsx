@@ -9,7 +9,13 @@ import {
 } from "@/components/ui/dialog";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
-import { useTimerStore, type TimerSettings } from "@/store/useTimerStore";
+import {
+  type ShortcutAction,
+  type ShortcutConflict,
+  useTimerStore,
+  type TimerSettings,
+} from "@/store/useTimerStore";
+import { invoke } from "@tauri-apps/api/core";
 import { Settings2 } from "lucide-react";
 import { useState } from "react";
 
@@ -41,9 +47,99 @@ function ToggleRow({
   );
 }
 
+function VolumeRow({
+  id,
+  label,
+  value,
+  onChange,
+}: Readonly<{
+  id: string;
+  label: string;
+  value: number;
+  onChange: (value: number) => void;
+}>) {
+  return (
+    <div className="flex items-center justify-between gap-4 p-1">
+      <Label htmlFor={id} className="font-medium opacity-80">
+        {label}
+      </Label>
+      <div className="flex items-center gap-2">
+        <input
+          id={id}
+          type="range"
+          min={0}
+          max={100}
+          value={value}
+          onChange={(e) => onChange(Number.parseInt(e.target.value))}
+          className="w-28 accent-primary cursor-pointer"
+        />
+        <span className="w-10 text-right text-sm tabular-nums opacity-70">
+          {value}%
+        </span>
+      </div>
+    </div>
+  );
+}
+
+function ShortcutRow({
+  id,
+  label,
+  value,
+  conflict,
+  onChange,
+}: Readonly<{
+  id: string;
+  label: string;
+  value: string;
+  conflict?: ShortcutConflict;
+  onChange: (value: string) => void;
+}>) {
+  return (
+    <div className="p-1">
+      <div className="flex items-center justify-between gap-4">
+        <Label htmlFor={id} className="font-medium opacity-80">
+          {label}
+        </Label>
+        <Input
+          id={id}
+          value={value}
+          placeholder="Atanmamış"
+          spellCheck={false}
+          onChange={(e) => onChange(e.target.value.toLowerCase())}
+          className="w-36 h-8 bg-background/50 border-white/10 focus:border-primary/50 transition-colors font-mono text-xs"
+        />
+      </div>
+      {conflict && (
+        <p className="pt-1 text-xs text-destructive">{conflict.message}</p>
+      )}
+    </div>
+  );
+}
+
+const SHORTCUT_LABELS: Record<ShortcutAction, string> = {
+  toggle: "Başlat/Duraklat",
+  skip: "Atla",
+  reset: "Sıfırla",
+  extend: "Uzat (+1 dk)",
+  showWindow: "Pencereyi Göster",
+  startBreak: "Molayı Başlat",
+};
+
+/** Rust tarafındaki SoundPack (bkz. sounds.rs) */
+interface SoundPack {
+  id: string;
+  name: string;
+  events: string[];
+  errors: string[];
+}
+
 export function SettingsModal() {
   const { settings, updateSettings } = useTimerStore();
   const [tempSettings, setTempSettings] = useState<TimerSettings>(settings);
+  const [soundPacks, setSoundPacks] = useState<SoundPack[]>([]);
+  const [shortcutConflicts, setShortcutConflicts] = useState<
+    ShortcutConflict[]
+  >([]);
 
   const handleSave = () => {
     updateSettings(tempSettings);
@@ -54,7 +150,19 @@ export function SettingsModal() {
   };
 
   return (
-    <Dialog onOpenChange={(open) => open && setTempSettings(settings)}>
+    <Dialog
+      onOpenChange={(open) => {
+        if (!open) return;
+        setTempSettings(settings);
+        // Paket dizini her açılışta yeniden taranır
+        invoke<SoundPack[]>("list_sound_packs")
+          .then(setSoundPacks)
+          .catch(() => {});
+        invoke<ShortcutConflict[]>("get_shortcut_conflicts")
+          .then(setShortcutConflicts)
+          .catch(() => {});
+      }}
+    >
       <DialogTrigger asChild>
         <Button
           variant="ghost"
@@ -118,6 +226,81 @@ export function SettingsModal() {
             </div>
           </div>
 
+          {/* Bildirim Ayarları */}
+          <div className="flex items-center justify-between gap-4 p-2 rounded-xl bg-white/5 border border-white/5">
+            <Label htmlFor="warningSeconds" className="font-medium">
+              Bitiş Uyarısı (sn, 0 = kapalı)
+            </Label>
+            <Input
+              id="warningSeconds"
+              type="number"
+              min={0}
+              value={tempSettings.warningSeconds}
+              onChange={(e) =>
+                update({
+                  warningSeconds: Math.max(
+                    0,
+                    Number.parseInt(e.target.value) || 0,
+                  ),
+                })
+              }
+              className="w-24 h-8 bg-background/50 border-white/10 focus:border-primary/50 transition-colors"
+            />
+          </div>
+          <div className="flex items-center justify-between gap-4 p-2 rounded-xl bg-white/5 border border-white/5">
+            <Label htmlFor="countdownSeconds" className="font-medium">
+              Geri Sayım Tıkları (sn, 0 = kapalı)
+            </Label>
+            <Input
+              id="countdownSeconds"
+              type="number"
+              min={0}
+              max={10}
+              value={tempSettings.countdownSeconds}
+              onChange={(e) =>
+                update({
+                  countdownSeconds: Math.min(
+                    10,
+                    Math.max(0, Number.parseInt(e.target.value) || 0),
+                  ),
+                })
+              }
+              className="w-24 h-8 bg-background/50 border-white/10 focus:border-primary/50 transition-colors"
+            />
+          </div>
+          <ToggleRow
+            id="warning-sound"
+            label="Bitiş Uyarısında Çan Çal"
+            checked={tempSettings.warningSound}
+            onChange={(checked) => update({ warningSound: checked })}
+          />
+          <ToggleRow
+            id="announce-transitions"
+            label="Oturum Geçişlerini Sesli Duyur"
+            checked={tempSettings.announceTransitions}
+            onChange={(checked) => update({ announceTransitions: checked })}
+          />
+          {tempSettings.announceTransitions && (
+            <div className="flex items-center justify-between p-1">
+              <Label htmlFor="language" className="font-medium opacity-80">
+                Duyuru Dili
+              </Label>
+              <select
+                id="language"
+                className="h-9 w-32 rounded-lg border border-white/10 bg-white/5 px-3 text-sm focus:ring-1 focus:ring-primary outline-none cursor-pointer"
+                value={tempSettings.language}
+                onChange={(e) =>
+                  update({
+                    language: e.target.value as TimerSettings["language"],
+                  })
+                }
+              >
+                <option value="tr">Türkçe</option>
+                <option value="en">English</option>
+              </select>
+            </div>
+          )}
+
           {/* Ses ve Gelişmiş Ayarlar */}
           <div className="space-y-2 pt-2 border-t border-white/10">
             <div className="flex items-center justify-between p-1">
@@ -139,6 +322,95 @@ export function SettingsModal() {
                 <option value="none">Kapalı</option>
                 <option value="clock">Saat</option>
                 <option value="timer">Dijital</option>
+                <option value="metronome">Metronom</option>
+              </select>
+            </div>
+
+            {tempSettings.tickingSound === "metronome" && (
+              <div className="flex items-center justify-between p-1">
+                <Label htmlFor="metronome-bpm" className="font-medium opacity-80">
+                  Metronom (BPM)
+                </Label>
+                <Input
+                  id="metronome-bpm"
+                  type="number"
+                  min={20}
+                  max={240}
+                  value={tempSettings.metronomeBpm}
+                  onChange={(e) =>
+                    update({
+                      metronomeBpm: Math.min(
+                        240,
+                        Math.max(20, Number.parseInt(e.target.value) || 60),
+                      ),
+                    })
+                  }
+                  className="w-24 h-8 bg-background/50 border-white/10 focus:border-primary/50 transition-colors"
+                />
+              </div>
+            )}
+
+            <div className="flex items-center justify-between p-1">
+              <Label htmlFor="focus-noise" className="font-medium opacity-80">
+                Odak Gürültüsü
+              </Label>
+              <select
+                id="focus-noise"
+                className="h-9 w-32 rounded-lg border border-white/10 bg-white/5 px-3 text-sm focus:ring-1 focus:ring-primary outline-none cursor-pointer"
+                value={tempSettings.focusNoise}
+                onChange={(e) =>
+                  update({
+                    focusNoise: e.target.value as TimerSettings["focusNoise"],
+                  })
+                }
+              >
+                <option value="none">Kapalı</option>
+                <option value="white">Beyaz</option>
+                <option value="pink">Pembe</option>
+                <option value="brown">Kahverengi</option>
+              </select>
+            </div>
+
+            <div className="flex items-center justify-between p-1">
+              <Label htmlFor="sound-pack" className="font-medium opacity-80">
+                Ses Paketi
+              </Label>
+              <select
+                id="sound-pack"
+                className="h-9 w-32 rounded-lg border border-white/10 bg-white/5 px-3 text-sm focus:ring-1 focus:ring-primary outline-none cursor-pointer"
+                value={tempSettings.soundPack}
+                onChange={(e) => update({ soundPack: e.target.value })}
+              >
+                <option value="">Varsayılan</option>
+                {soundPacks.map((pack) => (
+                  <option
+                    key={pack.id}
+                    value={pack.id}
+                    title={pack.errors.join("\n") || undefined}
+                  >
+                    {pack.name}
+                  </option>
+                ))}
+              </select>
+            </div>
+
+            <div className="flex items-center justify-between p-1">
+              <Label htmlFor="media-policy" className="font-medium opacity-80">
+                Medya Oynatıcı
+              </Label>
+              <select
+                id="media-policy"
+                className="h-9 w-32 rounded-lg border border-white/10 bg-white/5 px-3 text-sm focus:ring-1 focus:ring-primary outline-none cursor-pointer"
+                value={tempSettings.mediaPolicy}
+                onChange={(e) =>
+                  update({
+                    mediaPolicy: e.target.value as TimerSettings["mediaPolicy"],
+                  })
+                }
+              >
+                <option value="none">Dokunma</option>
+                <option value="pauseOnBreak">Molada Duraklat</option>
+                <option value="playOnWork">Odakta Çal</option>
               </select>
             </div>
 
@@ -148,12 +420,51 @@ export function SettingsModal() {
               checked={tempSettings.enableBreakSound}
               onChange={(checked) => update({ enableBreakSound: checked })}
             />
+
+            <VolumeRow
+              id="volume-ticking"
+              label="Tıkırtı Sesi Düzeyi"
+              value={tempSettings.volumes.ticking}
+              onChange={(ticking) =>
+                update({ volumes: { ...tempSettings.volumes, ticking } })
+              }
+            />
+            <VolumeRow
+              id="volume-ambient"
+              label="Ortam Sesi Düzeyi"
+              value={tempSettings.volumes.ambient}
+              onChange={(ambient) =>
+                update({ volumes: { ...tempSettings.volumes, ambient } })
+              }
+            />
+            <VolumeRow
+              id="volume-alerts"
+              label="Uyarı Sesi Düzeyi"
+              value={tempSettings.volumes.alerts}
+              onChange={(alerts) =>
+                update({ volumes: { ...tempSettings.volumes, alerts } })
+              }
+            />
             <ToggleRow
               id="strict-break"
               label="Sıkı Mola (Tam Ekran)"
               checked={tempSettings.enableStrictBreak}
               onChange={(checked) => update({ enableStrictBreak: checked })}
             />
+            <ToggleRow
+              id="dynamic-tray-icon"
+              label="Tray İkonunda İlerleme Halkası"
+              checked={tempSettings.dynamicTrayIcon}
+              onChange={(checked) => update({ dynamicTrayIcon: checked })}
+            />
+            {tempSettings.dynamicTrayIcon && (
+              <ToggleRow
+                id="tray-icon-minutes"
+                label="Tray İkonunda Kalan Dakika"
+                checked={tempSettings.trayIconMinutes}
+                onChange={(checked) => update({ trayIconMinutes: checked })}
+              />
+            )}
           </div>
 
           <div className="space-y-2 pt-2 border-t border-white/10">
@@ -169,6 +480,42 @@ export function SettingsModal() {
               checked={tempSettings.autoStartWork}
               onChange={(checked) => update({ autoStartWork: checked })}
             />
+            <ToggleRow
+              id="inhibit-sleep"
+              label="Odaklanırken Uykuyu Engelle"
+              checked={tempSettings.inhibitSleep}
+              onChange={(checked) => update({ inhibitSleep: checked })}
+            />
+            <ToggleRow
+              id="start-minimized"
+              label="Tray'de Başlat"
+              checked={tempSettings.startMinimized}
+              onChange={(checked) => update({ startMinimized: checked })}
+            />
+          </div>
+
+          {/* Global Kısayollar */}
+          <div className="space-y-1 pt-2 border-t border-white/10">
+            {(Object.keys(SHORTCUT_LABELS) as ShortcutAction[]).map(
+              (action) => (
+                <ShortcutRow
+                  key={action}
+                  id={`shortcut-${action}`}
+                  label={SHORTCUT_LABELS[action]}
+                  value={tempSettings.shortcuts[action]}
+                  conflict={
+                    tempSettings.shortcuts[action] === settings.shortcuts[action]
+                      ? shortcutConflicts.find((c) => c.action === action)
+                      : undefined
+                  }
+                  onChange={(value) =>
+                    update({
+                      shortcuts: { ...tempSettings.shortcuts, [action]: value },
+                    })
+                  }
+                />
+              ),
+            )}
           </div>
         </div>
 
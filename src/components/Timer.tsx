@@ -65,37 +65,62 @@ function useSoundEffect(
     enableTicking: boolean;
     tickingSound: string;
     enableBreakSound: boolean;
+    focusNoise: string;
   },
 ) {
   useEffect(() => {
+    // Döngü sesleri kendi kanallarında çalar; uyarı sesleri (alerts) kesilmez
+    const stop = (channel: string) =>
+      invoke("stop_sound", { channel }).catch(() => {});
+    const stopLoops = () => {
+      stop("ticking");
+      stop("ambient");
+    };
+
     if (!isActive) {
-      invoke("stop_sound").catch(() => {});
+      stopLoops();
       return;
     }
 
-    if (
-      mode === "work" &&
-      settings.enableTicking &&
-      settings.tickingSound !== "none"
-    ) {
-      invoke("play_sound_loop", { name: settings.tickingSound }).catch(
-        () => {},
-      );
-    } else if (mode !== "work" && settings.enableBreakSound) {
-      invoke("play_sound_loop", { name: "birds" }).catch(() => {});
+    if (mode === "work") {
+      // Tıkırtı kanalı: dosya veya üretilen metronom
+      if (!settings.enableTicking || settings.tickingSound === "none") {
+        stop("ticking");
+      } else if (settings.tickingSound === "metronome") {
+        invoke("play_metronome").catch(() => {});
+      } else {
+        invoke("play_sound_loop", {
+          name: settings.tickingSound,
+          channel: "ticking",
+          event: "tick",
+        }).catch(() => {});
+      }
+
+      // Ortam kanalı: üretilen gürültü
+      if (settings.focusNoise === "none") {
+        stop("ambient");
+      } else {
+        invoke("play_noise", { color: settings.focusNoise }).catch(() => {});
+      }
     } else {
-      invoke("stop_sound").catch(() => {});
+      stop("ticking");
+      if (settings.enableBreakSound) {
+        invoke("play_sound_loop", { name: "birds", channel: "ambient" }).catch(
+          () => {},
+        );
+      } else {
+        stop("ambient");
+      }
     }
 
-    return () => {
-      invoke("stop_sound").catch(() => {});
-    };
+    return stopLoops;
   }, [
     isActive,
     mode,
     settings.enableTicking,
     settings.tickingSound,
     settings.enableBreakSound,
+    settings.focusNoise,
   ]);
 }
 
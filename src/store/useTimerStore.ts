@@ -8,45 +8,71 @@ export interface TimerSettings {
   longBreak: number;
   longBreakInterval: number;
   enableTicking: boolean;
-  tickingSound: "clock" | "timer" | "none";
+  tickingSound: "clock" | "timer" | "metronome" | "none";
+  metronomeBpm: number;
+  /** Çalışma sırasında üretilen arka plan gürültüsü */
+  focusNoise: "none" | "white" | "pink" | "brown";
   enableBreakSound: boolean;
   enableStrictBreak: boolean;
   autoStartBreaks: boolean;
   autoStartWork: boolean;
   pauseWhenIdle: boolean;
+  /** Tray ikonunda kalan süreyi halka olarak göster */
+  dynamicTrayIcon: boolean;
+  /** Dinamik tray ikonunda kalan dakikayı yaz */
+  trayIconMinutes: boolean;
+  inhibitSleep: boolean;
+  /** Uygulama açılırken pencereyi gösterme, yalnızca tray'de başla */
+  startMinimized: boolean;
+  warningSeconds: number;
+  /** Bitiş uyarısında çan çal (warningSeconds kala) */
+  warningSound: boolean;
+  /** Son kaç saniyede geri sayım tıkı çalınsın (0 = kapalı) */
+  countdownSeconds: number;
+  /** Oturum bitişlerinde sesli duyuru (spd-say / espeak-ng) */
+  announceTransitions: boolean;
+  /** Sesli duyuruların dili */
+  language: "tr" | "en";
+  mediaPolicy: "none" | "pauseOnBreak" | "playOnWork";
+  volumes: Record<SoundChannel, number>;
+  /** Seçili ses paketinin dizin adı ("" = uygulamayla gelen sesler) */
+  soundPack: string;
+  /** Global kısayollar ("" = atanmamış), ör. "ctrl+alt+p" */
+  shortcuts: Record<ShortcutAction, string>;
 }
 
-type TimerMode = "work" | "shortBreak" | "longBreak";
+/** Rust mikserindeki ses kanalları (bkz. audio.rs) */
+export type SoundChannel = "ticking" | "ambient" | "alerts";
 
-function getNotificationBody(
-  previousMode: string,
-  currentMode: string,
-): string {
-  if (previousMode !== "work") return "Mola bitti, çalışmaya devam!";
-  return currentMode === "longBreak"
-    ? "Harika iş! Uzun mola zamanı."
-    : "İyi çalışma! Kısa mola zamanı.";
-}
+/** Global kısayol atanabilen eylemler (bkz. shortcuts.rs) */
+export type ShortcutAction =
+  | "toggle"
+  | "skip"
+  | "reset"
+  | "extend"
+  | "showWindow"
+  | "startBreak";
 
-async function sendTimerNotification(body: string) {
-  const { isPermissionGranted, requestPermission, sendNotification } =
-    await import("@tauri-apps/plugin-notification");
-  let granted = await isPermissionGranted();
-  if (!granted) {
-    const permission = await requestPermission();
-    granted = permission === "granted";
-  }
-  if (granted) {
-    sendNotification({ title: "Pomodoro", body });
-  }
+/** Kaydedilemeyen bir global kısayol (bkz. shortcuts.rs) */
+export interface ShortcutConflict {
+  action: ShortcutAction;
+  accelerator: string;
+  kind: "invalid" | "duplicate" | "unavailable";
+  message: string;
 }
 
+type TimerMode = "work" | "shortBreak" | "longBreak";
+
+/** Son mod değişikliğinin nedeni (Rust'taki bildirim ve olaylar için) */
+type TransitionReason = "finished" | "skipped";
+
 interface TimerState {
   timeLeft: number;
   isActive: boolean;
   mode: TimerMode;
   sessionsCompleted: number;
   lastTickTimestamp: number;
+  transitionReason: TransitionReason | null;
   settings: TimerSettings;
   // Actions
   tick: () => void;
@@ -68,6 +94,7 @@ export const useTimerStore = create<TimerState>()(
       mode: "work",
       sessionsCompleted: 0,
       lastTickTimestamp: 0,
+      transitionReason: null,
       settings: {
         work: 25,
         shortBreak: 5,
@@ -75,11 +102,33 @@ export const useTimerStore = create<TimerState>()(
         longBreakInterval: 4,
         enableTicking: false,
         tickingSound: "clock",
+        metronomeBpm: 60,
+        focusNoise: "none",
         enableBreakSound: true,
         enableStrictBreak: false,
         autoStartBreaks: false,
         autoStartWork: false,
         pauseWhenIdle: false,
+        dynamicTrayIcon: true,
+        trayIconMinutes: false,
+        inhibitSleep: false,
+        startMinimized: false,
+        warningSeconds: 60,
+        warningSound: false,
+        countdownSeconds: 0,
+        announceTransitions: false,
+        language: "tr",
+        mediaPolicy: "none",
+        volumes: { ticking: 100, ambient: 100, alerts: 100 },
+        soundPack: "",
+        shortcuts: {
+          toggle: "ctrl+alt+p",
+          skip: "",
+          reset: "",
+          extend: "",
+          showWindow: "",
+          startBreak: "",
+        },
       },
 
       tick: () => {
@@ -109,6 +158,7 @@ export const useTimerStore = create<TimerState>()(
               isActive: settings.autoStartBreaks,
               mode: nextMode,
               sessionsCompleted: newSessions,
+              transitionReason: "finished",
             });
           } else {
             // Break bitti → work'e geç
@@ -116,6 +166,7 @@ export const useTimerStore = create<TimerState>()(
               timeLeft: settings.work * 60,
               isActive: settings.autoStartWork,
               mode: "work",
+              transitionReason: "finished",
             });
           }
 
@@ -123,13 +174,12 @@ export const useTimerStore = create<TimerState>()(
           const elapsed = settings[mode] * 60;
           invoke("save_session", { state: mode, elapsed }).catch(() => {});
 
-          // Bildirim gönder ve ses çal
+          // Ses çal (bildirimler Rust tarafından gönderilir)
           const soundName = mode === "work" ? "bell" : "loud-bell";
-          invoke("play_sound", { name: soundName }).catch(() => {});
-
-          const { mode: currentMode } = get();
-          const body = getNotificationBody(mode, currentMode);
-          sendTimerNotification(body).catch(() => {});
+          const soundEvent = mode === "work" ? "workEnd" : "breakEnd";
+          invoke("play_sound", { name: soundName, event: soundEvent }).catch(
+            () => {},
+          );
         }
       },
 
@@ -145,6 +195,7 @@ export const useTimerStore = create<TimerState>()(
           sessionsCompleted: newSessions,
           mode: nextMode,
           timeLeft: settings[nextMode] * 60,
+          transitionReason: "skipped",
         });
       },
 
@@ -181,6 +232,7 @@ export const useTimerStore = create<TimerState>()(
             mode: nextMode,
             timeLeft: settings[nextMode] * 60,
             isActive: false,
+            transitionReason: "skipped",
           });
         } else {
           // Break → work
@@ -188,6 +240,7 @@ export const useTimerStore = create<TimerState>()(
             mode: "work",
             timeLeft: settings.work * 60,
             isActive: false,
+            transitionReason: "skipped",
           });
         }
       },
@@ -205,6 +258,7 @@ export const useTimerStore = create<TimerState>()(
           mode,
           timeLeft: settings[mode] * 60,
           isActive: false,
+          transitionReason: "skipped",
         });
       },
 
@@ -223,6 +277,23 @@ export const useTimerStore = create<TimerState>()(
     }),
     {
       name: "pomodoro-storage",
+      // Yeni eklenen ayarlar eski kayıtlarda yok; varsayılanlarla birleştir
+      merge: (persisted, current) => {
+        const saved = persisted as Partial<TimerState> | undefined;
+        return {
+          ...current,
+          ...saved,
+          settings: {
+            ...current.settings,
+            ...saved?.settings,
+            volumes: { ...current.settings.volumes, ...saved?.settings?.volumes },
+            shortcuts: {
+              ...current.settings.shortcuts,
+              ...saved?.settings?.shortcuts,
+            },
+          },
+        };
+      },
       partialize: (state) => ({
         timeLeft: state.timeLeft,
         isActive: state.isActive,